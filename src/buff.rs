@@ -0,0 +1,189 @@
+//! 增益系统模块
+//!
+//! 处理道具带来的限时增益：叠加、计时和到期撤销
+//!
+//! 叠加规则：
+//! - 数值类增益（速度、护甲、射速、子弹数）每次拾取都叠加数值（不超过上限），并把计时刷新为完整时长
+//! - 开关类增益（火焰弹、履带链、穿透、气垫、跳弹）重复拾取只刷新计时
+//! - 到期时撤销该增益累计带来的全部变化
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::constants::{BubbleEffect, PlayerTank, PowerUp, SEA_GROUP};
+use crate::resources::{Buff, PlayerInfo, PlayerStatChanged, PlayerStats, StatType};
+
+/// 各增益的持续时间（秒），None 表示永久生效
+const fn buff_duration(stat_type: StatType) -> Option<f32> {
+    match stat_type {
+        StatType::Speed | StatType::Protection | StatType::FireSpeed | StatType::Shell => Some(30.0),
+        StatType::FireShell | StatType::TrackChain | StatType::Penetrate | StatType::Ricochet => Some(20.0),
+        StatType::AirCushion => Some(15.0),
+        StatType::Score | StatType::Missile | StatType::Lives => None,
+    }
+}
+
+/// 提升数值，不超过上限，返回实际增加的数值
+fn raise_stat(value: &mut usize, step: usize, max: usize) -> usize {
+    let raised = (*value + step).min(max).max(*value);
+    let applied = raised - *value;
+    *value = raised;
+    applied
+}
+
+/// 道具对应的增益属性（即时生效的道具返回 None）
+pub const fn powerup_buff_stat(powerup_type: PowerUp) -> Option<StatType> {
+    match powerup_type {
+        PowerUp::SpeedUp => Some(StatType::Speed),
+        PowerUp::Protection => Some(StatType::Protection),
+        PowerUp::FireSpeed => Some(StatType::FireSpeed),
+        PowerUp::FireShell => Some(StatType::FireShell),
+        PowerUp::TrackChain => Some(StatType::TrackChain),
+        PowerUp::Penetrate => Some(StatType::Penetrate),
+        PowerUp::AirCushion => Some(StatType::AirCushion),
+        PowerUp::Shell => Some(StatType::Shell),
+        PowerUp::Ricochet => Some(StatType::Ricochet),
+        PowerUp::Repair | PowerUp::Hamburger | PowerUp::Clock | PowerUp::Shovel | PowerUp::Grenade | PowerUp::Missile => None,
+    }
+}
+
+/// 应用增益：修改玩家属性并记录（或刷新）对应的增益计时
+pub fn apply_buff(player_stats: &mut PlayerStats, stat_type: StatType) {
+    let applied = match stat_type {
+        StatType::Speed => raise_stat(&mut player_stats.speed, 20, 100),
+        StatType::Protection => raise_stat(&mut player_stats.protection, 20, 100),
+        StatType::FireSpeed => raise_stat(&mut player_stats.fire_speed, 20, 100),
+        StatType::Shell => raise_stat(&mut player_stats.shells, 1, 2), // 最多 2 颗
+        StatType::FireShell => {
+            player_stats.fire_shell = true;
+            0
+        }
+        StatType::TrackChain => {
+            player_stats.track_chain = true;
+            0
+        }
+        StatType::Penetrate => {
+            player_stats.penetrate = true;
+            0
+        }
+        StatType::AirCushion => {
+            player_stats.air_cushion = true;
+            0
+        }
+        StatType::Ricochet => {
+            player_stats.ricochet = true;
+            0
+        }
+        StatType::Score | StatType::Missile | StatType::Lives => return,
+    };
+
+    let timer = buff_duration(stat_type).map(|secs| Timer::from_seconds(secs, TimerMode::Once));
+    if let Some(buff) = player_stats.buffs.iter_mut().find(|buff| buff.stat_type == stat_type) {
+        buff.amount += applied;
+        buff.timer = timer;
+    } else {
+        player_stats.buffs.push(Buff {
+            stat_type,
+            amount: applied,
+            timer,
+        });
+    }
+}
+
+/// 撤销增益带来的属性变化
+fn revert_buff(player_stats: &mut PlayerStats, buff: &Buff) {
+    match buff.stat_type {
+        StatType::Speed => player_stats.speed = player_stats.speed.saturating_sub(buff.amount),
+        StatType::Protection => player_stats.protection = player_stats.protection.saturating_sub(buff.amount),
+        StatType::FireSpeed => player_stats.fire_speed = player_stats.fire_speed.saturating_sub(buff.amount),
+        StatType::Shell => player_stats.shells = player_stats.shells.saturating_sub(buff.amount).max(1),
+        StatType::FireShell => player_stats.fire_shell = false,
+        StatType::TrackChain => player_stats.track_chain = false,
+        StatType::Penetrate => player_stats.penetrate = false,
+        StatType::AirCushion => player_stats.air_cushion = false,
+        StatType::Ricochet => player_stats.ricochet = false,
+        StatType::Score | StatType::Missile | StatType::Lives => {}
+    }
+}
+
+/// 增益已被其他途径消耗（如中弹抵挡）时同步记录，避免到期时重复撤销
+pub fn discard_buff(player_stats: &mut PlayerStats, stat_type: StatType, amount: usize) {
+    if let Some(index) = player_stats.buffs.iter().position(|buff| buff.stat_type == stat_type) {
+        let buff = &mut player_stats.buffs[index];
+        buff.amount = buff.amount.saturating_sub(amount);
+        if buff.amount == 0 {
+            player_stats.buffs.remove(index);
+        }
+    }
+}
+
+/// 换关时结束依附于坦克实体的增益：气垫的碰撞过滤和气泡特效随坦克一起销毁，其余增益带到下一关继续计时
+pub fn end_stage_buffs(player_stats: &mut PlayerStats) {
+    if let Some(index) = player_stats.buffs.iter().position(|buff| buff.stat_type == StatType::AirCushion) {
+        let buff = player_stats.buffs.remove(index);
+        revert_buff(player_stats, &buff);
+    }
+}
+
+/// 开启或关闭气垫：调整与海（`GROUP_2`）的碰撞过滤，并增删气泡特效标记
+pub fn set_air_cushion(
+    commands: &mut Commands,
+    controller: Option<Mut<KinematicCharacterController>>,
+    tank_entity: Entity,
+    enabled: bool,
+) {
+    if enabled {
+        // 玩家坦克不设置 memberships（默认所有组），filters 设置为不包含 GROUP_2
+        if let Some(mut controller) = controller {
+            controller.filter_groups = Some(CollisionGroups::new(Group::all(), Group::all() & !SEA_GROUP));
+        }
+        commands.entity(tank_entity).insert(BubbleEffect);
+    } else {
+        if let Some(mut controller) = controller {
+            controller.filter_groups = None;
+        }
+        commands.entity(tank_entity).remove::<BubbleEffect>();
+    }
+}
+
+/// 增益计时系统：到期后撤销增益并通知 HUD
+pub fn update_buffs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut player_tanks: Query<(Entity, &PlayerTank, &mut KinematicCharacterController)>,
+    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+) {
+    for (player_type, player_stats) in &mut player_info.players {
+        let mut expired = Vec::new();
+        player_stats.buffs.retain_mut(|buff| {
+            let Some(timer) = buff.timer.as_mut() else {
+                return true;
+            };
+            timer.tick(time.delta());
+            if timer.is_finished() {
+                expired.push(buff.clone());
+                false
+            } else {
+                true
+            }
+        });
+
+        for buff in expired {
+            revert_buff(player_stats, &buff);
+
+            if buff.stat_type == StatType::AirCushion {
+                for (tank_entity, player_tank, controller) in &mut player_tanks {
+                    if player_tank.tank_type == *player_type {
+                        set_air_cushion(&mut commands, Some(controller), tank_entity, false);
+                    }
+                }
+            }
+
+            stat_changed_events.write(PlayerStatChanged {
+                player_type: *player_type,
+                stat_type: buff.stat_type,
+            });
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! 野战工事模块
+//!
+//! 处理玩家消耗蓝条在坦克前方的格子里砌砖，用来在关卡中修补司令官堡垒的缺口
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::constants::{
+    BRICK_HEIGHT, BRICK_WIDTH, BUILD_ENERGY_COST, Barrier, Brick, COMMANDER_WIDTH, Commander,
+    ENEMY_BORN_PLACES, EnemyTank, PLAYER1_START_POSITION, PLAYER2_START_POSITION, PlayerTank,
+    PlayingEntity, RotationTimer, SOUND_BRICK_HIT, Sea, Steel, TANK_WIDTH, TEXTURE_BRICK, TankType,
+};
+use crate::map::{grid_to_world, world_to_grid, GRID_SIZE};
+use crate::powerup::{commander_wall_positions_for, spawn_commander_wall_block};
+use crate::resources::{GameMode, PlayerInfo};
+
+/// 两个正方形区域是否重叠（边缘相接不算重叠）
+pub fn overlaps(a: Vec2, a_half: f32, b: Vec2, b_half: f32) -> bool {
+    let limit = a_half + b_half;
+    (a.x - b.x).abs() < limit && (a.y - b.y).abs() < limit
+}
+
+/// 玩家砌砖系统
+/// 目标格子按砖块大小分成 4 块，只在没有地形、坦克、司令官和出生点的位置砌砖；
+/// 一块都砌不了时不消耗蓝条
+pub fn player_build_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    builders: Query<(&Transform, &RotationTimer, &PlayerTank)>,
+    terrain: Query<&Transform, Or<(With<Brick>, With<Steel>)>>,
+    large_terrain: Query<&Transform, Or<(With<Sea>, With<Barrier>)>>,
+    tanks: Query<&Transform, Or<(With<PlayerTank>, With<EnemyTank>)>>,
+    commanders: Query<&Transform, With<Commander>>,
+    mut player_info: ResMut<PlayerInfo>,
+    game_mode: Res<GameMode>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+) {
+    for (transform, rotation_timer, player_tank) in &builders {
+        // 检查是否正在旋转
+        if rotation_timer.0.elapsed() < rotation_timer.0.duration() {
+            continue;
+        }
+
+        // 检查砌砖键
+        let build_key = match player_tank.tank_type {
+            TankType::Player1 => KeyCode::KeyH,
+            TankType::Player2 => KeyCode::Numpad7,
+            TankType::Enemy => continue,
+        };
+        if !keyboard.just_pressed(build_key) {
+            continue;
+        }
+
+        // 检查蓝量是否足够
+        let Some(player_stats) = player_info.players.get_mut(&player_tank.tank_type) else {
+            continue;
+        };
+        if player_stats.energy_blue_bar < BUILD_ENERGY_COST {
+            continue;
+        }
+
+        // 坦克前方一格（与射击方向相同），对齐到网格
+        let euler_angle = transform.rotation.to_euler(EulerRot::XYZ).2;
+        let actual_angle = euler_angle + 90.0_f32.to_radians();
+        let direction = Vec2::new(actual_angle.cos(), actual_angle.sin());
+        let Some((row, col)) = world_to_grid(transform.translation.truncate() + direction * GRID_SIZE) else {
+            continue;
+        };
+        let cell = grid_to_world(row, col);
+
+        let brick_half = BRICK_WIDTH / 2.0;
+        let spawn_points: Vec<Vec2> = ENEMY_BORN_PLACES
+            .iter()
+            .chain(&[PLAYER1_START_POSITION, PLAYER2_START_POSITION])
+            .map(|position| position.truncate())
+            .collect();
+
+        let offset = GRID_SIZE / 4.0;
+        let positions: Vec<Vec2> = [
+            Vec2::new(-offset, offset),
+            Vec2::new(offset, offset),
+            Vec2::new(-offset, -offset),
+            Vec2::new(offset, -offset),
+        ]
+        .into_iter()
+        .map(|brick_offset| cell + brick_offset)
+        .filter(|&position| {
+            !terrain.iter().any(|t| overlaps(position, brick_half, t.translation.truncate(), BRICK_WIDTH / 2.0))
+                && !large_terrain.iter().any(|t| overlaps(position, brick_half, t.translation.truncate(), GRID_SIZE / 2.0))
+                && !tanks.iter().any(|t| overlaps(position, brick_half, t.translation.truncate(), TANK_WIDTH / 2.0))
+                && !commanders.iter().any(|t| overlaps(position, brick_half, t.translation.truncate(), COMMANDER_WIDTH / 2.0))
+                && !spawn_points.iter().any(|&spawn| overlaps(position, brick_half, spawn, TANK_WIDTH / 2.0))
+        })
+        .collect();
+        if positions.is_empty() {
+            continue;
+        }
+
+        player_stats.energy_blue_bar -= BUILD_ENERGY_COST;
+
+        // 播放砌砖音效
+        let build_sound: Handle<AudioSource> = asset_server.load(SOUND_BRICK_HIT);
+        commands.spawn(AudioPlayer::new(build_sound));
+
+        let wall_positions = commander_wall_positions_for(*game_mode);
+        let brick_texture: Handle<Image> = asset_server.load(TEXTURE_BRICK);
+        for position in positions {
+            // 补在司令官堡垒缺口上的砖块算作堡垒墙，铁锹道具加固和到期恢复时一并处理
+            if wall_positions.iter().any(|wall| wall.distance(position) < 1.0) {
+                spawn_commander_wall_block(&mut commands, &asset_server, position, false);
+                continue;
+            }
+            commands.spawn((
+                Brick,
+                PlayingEntity,
+                Sprite {
+                    image: brick_texture.clone(),
+                    custom_size: Some(Vec2::new(BRICK_WIDTH, BRICK_HEIGHT)),
+                    ..default()
+                },
+                Transform::from_xyz(position.x, position.y, 0.0),
+                RigidBody::Fixed,
+                Collider::cuboid(BRICK_WIDTH / 2.0, BRICK_HEIGHT / 2.0),
+                ActiveEvents::COLLISION_EVENTS,
+                ActiveCollisionTypes::all(),
+            ));
+        }
+    }
+}
@@ -3,9 +3,12 @@
 //! 处理子弹的生成、移动、碰撞检测和销毁逻辑
 
 use bevy::prelude::*;
+use bevy_rapier2d::parry::query;
 use bevy_rapier2d::prelude::*;
+use bevy_rapier2d::rapier::math::{Isometry, Vector};
 use rand::Rng;
 
+use crate::combat::{Damage, DamageKind};
 use crate::constants::*;
 use crate::resources::*;
 
@@ -38,6 +41,28 @@ pub struct BulletOwner {
 #[derive(Component)]
 pub struct BulletDespawnMarker;
 
+/// 子弹命中标记组件：命中砖块、钢铁、坦克或司令官（导弹还包括外墙）时与销毁标记一起添加，
+/// 只有带这个标记销毁的爆炸弹才会爆炸（飞出边界、被抵消或被激光烧毁时不爆炸）
+#[derive(Component)]
+pub struct BulletImpactMarker;
+
+/// 爆炸弹组件：火焰弹效果下发射的炮弹，销毁时产生范围爆炸
+#[derive(Component)]
+pub struct ExplosiveShell;
+
+/// 爆炸弹爆炸事件
+#[derive(Message, Clone, Copy)]
+pub struct ShellExploded {
+    pub position: Vec3,
+    pub owner_type: TankType,
+}
+
+/// 跳弹组件：在钢铁和外墙上反弹，记录剩余反弹次数
+#[derive(Component)]
+pub struct Ricochet {
+    pub bounces_left: u32,
+}
+
 /// 子弹生成参数
 pub struct BulletSpawnParams {
     pub position: Vec3,
@@ -98,8 +123,9 @@ pub fn spawn_bullet(
 pub fn enemy_shoot_system(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut query: Query<(Entity, &Transform, &Velocity, &TankFireConfig), With<EnemyTank>>,
+    mut query: Query<(Entity, &Transform, &Velocity, &TankFireConfig), (With<EnemyTank>, Without<Frozen>)>,
     mut bullet_tracker: ResMut<BulletTracker>,
+    mut rng: ResMut<GameRng>,
 ) {
     for (entity, transform, velocity, fire_config) in &mut query {
         // 检查是否可以射击
@@ -108,7 +134,6 @@ pub fn enemy_shoot_system(
         }
 
         // 随机射击，每帧有 1.0% 的概率射击
-        let mut rng = rand::rng();
         if rng.random::<f32>() < 0.01 {
             // 计算子弹发射方向（基于坦克当前移动方向）
             let direction = if velocity.linvel.length() > 0.0 {
@@ -146,6 +171,7 @@ pub fn player_shoot_system(
     mut query: Query<(Entity, &Transform, &RotationTimer, &PlayerTank, &mut TankFireConfig), With<PlayerTank>>,
     mut bullet_tracker: ResMut<BulletTracker>,
     player_info: Res<PlayerInfo>,
+    mut stage_stats: ResMut<StageStats>,
     keyboard: Res<ButtonInput<KeyCode>>,
 ) {
     for (entity, transform, rotation_timer, player_tank, mut fire_config) in &mut query {
@@ -207,17 +233,57 @@ pub fn player_shoot_system(
             },
         );
 
+        // 火焰弹效果：炮弹命中时爆炸
+        if player_stats.fire_shell {
+            commands.entity(bullet_entity).insert(ExplosiveShell);
+        }
+
+        // 跳弹效果：炮弹可以反弹
+        if player_stats.ricochet {
+            commands.entity(bullet_entity).insert(Ricochet {
+                bounces_left: RICOCHET_MAX_BOUNCES,
+            });
+        }
+
         // 记录子弹的所有者
         bullet_tracker.add_bullet(bullet_entity, entity);
+        stage_stats.players.entry(player_tank.tank_type).or_default().shots_fired += 1;
 
         // 重置冷却时间
         fire_config.cooldown.reset();
     }
 }
 
+/// 跳弹反弹：按接触面法线镜像速度并衰减，反弹次数用尽时返回 false
+fn bounce_ricochet(ricochet: &mut Ricochet, velocity: &mut Velocity, normal: Vec2) -> bool {
+    if ricochet.bounces_left == 0 {
+        return false;
+    }
+    ricochet.bounces_left -= 1;
+    let reflected = velocity.linvel - 2.0 * velocity.linvel.dot(normal) * normal;
+    velocity.linvel = reflected * RICOCHET_SPEED_FACTOR;
+    true
+}
+
+/// 用碰撞体的几何形状计算子弹与障碍物的接触面法线（从障碍物指向子弹）
+fn contact_normal(obstacle: (&Transform, &Collider), bullet: (&Transform, &Collider)) -> Option<Vec2> {
+    let isometry = |transform: &Transform| {
+        let angle = transform.rotation.to_euler(EulerRot::ZYX).0;
+        Isometry::new(Vector::new(transform.translation.x, transform.translation.y), angle)
+    };
+    let contact = query::contact(&isometry(obstacle.0), &*obstacle.1.raw, &isometry(bullet.0), &*bullet.1.raw, 0.0)
+        .ok()
+        .flatten()?;
+    Some(Vec2::new(contact.normal1.x, contact.normal1.y))
+}
+
 /// 子弹边界检查系统
-pub fn bullet_bounds_check_system(mut commands: Commands, mut query: Query<(Entity, &Transform), With<Bullet>>) {
-    for (entity, transform) in &mut query {
+/// 子弹超出边界后销毁（跳弹在外墙上的反弹由碰撞系统处理）
+pub fn bullet_bounds_check_system(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform), With<Bullet>>,
+) {
+    for (entity, transform) in &query {
         let x = transform.translation.x;
         let y = transform.translation.y;
 
@@ -230,17 +296,37 @@ pub fn bullet_bounds_check_system(mut commands: Commands, mut query: Query<(Enti
     }
 }
 
+/// 跳弹朝向系统：反弹后让子弹贴图朝向新的飞行方向
+pub fn update_ricochet_rotation(
+    mut query: Query<(&Velocity, &mut Transform), (With<Ricochet>, Changed<Velocity>)>,
+) {
+    for (velocity, mut transform) in &mut query {
+        // 与 spawn_bullet 相同：纹理默认向右，按飞行方向旋转
+        let angle = velocity.linvel.y.atan2(velocity.linvel.x);
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+
 /// 子弹统一销毁系统
 /// 处理所有子弹的销毁逻辑，包括清理所有者引用和实际销毁
 pub fn bullet_despawn_system(
     mut commands: Commands,
-    mut query: Query<(Entity, &BulletDespawnMarker, &BulletOwner), With<Bullet>>,
+    mut query: Query<(Entity, &BulletDespawnMarker, &BulletOwner, &Transform, Has<ExplosiveShell>, Has<BulletImpactMarker>), With<Bullet>>,
     mut bullet_tracker: ResMut<BulletTracker>,
+    mut shell_exploded_events: MessageWriter<ShellExploded>,
 ) {
-    for (entity, _marker, _owner) in &mut query {
+    for (entity, _marker, owner, transform, is_explosive, is_impact) in &mut query {
         // 清理所有者引用，允许坦克再次射击
         bullet_tracker.remove_bullet(entity);
 
+        // 爆炸弹只在命中处爆炸
+        if is_explosive && is_impact {
+            shell_exploded_events.write(ShellExploded {
+                position: transform.translation,
+                owner_type: owner.owner_type,
+            });
+        }
+
         // 销毁子弹实体
         let _ = commands.entity(entity).try_despawn();
     }
@@ -267,11 +353,19 @@ pub fn find_bullet_and_tank_in_collision(
 /// 判断子弹是否应该销毁
 pub fn should_bullet_destroy(
     bullet_owner_type: TankType,
+    shooter: Option<Entity>,
     tank_entity: Entity,
     enemy_tanks: &Query<(), With<EnemyTank>>,
     player_tanks: &Query<&PlayerTank, With<PlayerTank>>,
+    match_options: &MatchOptions,
+    game_mode: GameMode,
 ) -> bool {
-    let is_player_tank = player_tanks.get(tank_entity).is_ok();
+    // 子弹刚射出时与自己的坦克重叠，永远不会打中自己
+    if shooter == Some(tank_entity) {
+        return false;
+    }
+
+    let target_player = player_tanks.get(tank_entity).ok().map(|player_tank| player_tank.tank_type);
     let is_enemy_tank = enemy_tanks.get(tank_entity).is_ok();
 
     let is_player_bullet = matches!(bullet_owner_type, TankType::Player1 | TankType::Player2);
@@ -280,9 +374,15 @@ pub fn should_bullet_destroy(
     // 规则：
     // 1. 玩家子弹打到敌方坦克 -> 子弹消失
     // 2. 敌方子弹打到玩家坦克 -> 子弹消失
-    // 3. 敌方子弹打到敌方坦克 -> 子弹穿过（不消失）
-    // 4. 玩家子弹打到玩家坦克 -> 子弹穿过（不消失）
-    (is_player_bullet && is_enemy_tank) || (is_enemy_bullet && is_player_tank)
+    // 3. 敌方子弹打到敌方坦克 -> 开启敌方误伤时子弹消失，否则穿过
+    // 4. 玩家子弹打到另一名玩家坦克 -> 开启友军误伤或对战模式时子弹消失，否则穿过
+    // 5. 玩家子弹打到自己的坦克 -> 子弹穿过（不消失）
+    (is_player_bullet && is_enemy_tank)
+        || (is_enemy_bullet && target_player.is_some())
+        || (is_enemy_bullet && is_enemy_tank && match_options.enemy_friendly_fire)
+        || (is_player_bullet
+            && match_options.players_can_hurt_each_other(game_mode)
+            && target_player.is_some_and(|target| target != bullet_owner_type))
 }
 
 /// 子弹与地形碰撞检测系统
@@ -297,7 +397,11 @@ pub fn bullet_terrain_collision_system(
     forests: Query<(Entity, &Transform), With<Forest>>,
     bricks: Query<(), With<Brick>>,
     steels: Query<(), With<Steel>>,
+    walls: Query<(), With<Wall>>,
+    missiles: Query<(), With<crate::missile::Missile>>,
+    colliders: Query<(&Transform, &Collider)>,
     player_info: Res<PlayerInfo>,
+    mut ricochets: Query<(&mut Ricochet, &mut Velocity), With<Bullet>>,
 ) {
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
@@ -345,10 +449,45 @@ pub fn bullet_terrain_collision_system(
 
                 // 销毁砖块和标记子弹销毁
                 let _ = commands.entity(terrain_entity).try_despawn();
-                commands.entity(bullet_entity).try_insert(BulletDespawnMarker);
+                commands.entity(bullet_entity).try_insert((BulletDespawnMarker, BulletImpactMarker));
+            } else if walls.get(terrain_entity).is_ok() {
+                // 导弹撞上外墙时爆炸，跳弹在外墙上反弹，其余子弹由边界检查系统销毁
+                if missiles.contains(bullet_entity) {
+                    commands.entity(bullet_entity).try_insert((BulletDespawnMarker, BulletImpactMarker));
+                } else if let Ok((mut ricochet, mut velocity)) = ricochets.get_mut(bullet_entity)
+                    && let (Ok(wall), Ok(bullet)) = (colliders.get(terrain_entity), colliders.get(bullet_entity))
+                    && let Some(normal) = contact_normal(wall, bullet)
+                    && bounce_ricochet(&mut ricochet, &mut velocity, normal)
+                {
+                    effect_events.write(EffectEvent::Spark {
+                        position: bullet_transform.translation,
+                    });
+                }
             } else if steels.get(terrain_entity).is_ok() {
                 // 子弹与钢铁碰撞
                 let player_index = bullet_owner.owner_type;
+
+                // 跳弹（没有穿透效果时）在钢铁上反弹
+                let has_penetrate = player_info.players.get(&player_index).is_some_and(|stats| stats.penetrate);
+                if !has_penetrate
+                    && let Ok((mut ricochet, mut velocity)) = ricochets.get_mut(bullet_entity)
+                    && let (Ok(steel), Ok(bullet)) = (colliders.get(terrain_entity), colliders.get(bullet_entity))
+                    && let Some(normal) = contact_normal(steel, bullet)
+                {
+                    if velocity.linvel.dot(normal) >= 0.0 {
+                        // 本帧已在相邻钢块上反弹过，正在离开
+                        continue;
+                    }
+                    if bounce_ricochet(&mut ricochet, &mut velocity, normal) {
+                        let hit_sound: Handle<AudioSource> = asset_server.load(SOUND_HIT);
+                        commands.spawn(AudioPlayer::new(hit_sound));
+                        effect_events.write(EffectEvent::Spark {
+                            position: bullet_transform.translation,
+                        });
+                        continue;
+                    }
+                }
+
                 if player_index != TankType::Enemy {
                     if let Some(player_stats) = player_info.players.get(&player_index) {
                         if player_stats.penetrate {
@@ -363,7 +502,7 @@ pub fn bullet_terrain_collision_system(
 
                             // 销毁钢铁和标记子弹销毁
                             let _ = commands.entity(terrain_entity).try_despawn();
-                            commands.entity(bullet_entity).try_insert(BulletDespawnMarker);
+                            commands.entity(bullet_entity).try_insert((BulletDespawnMarker, BulletImpactMarker));
                         } else {
                             // 没有 penetrate 效果，只播放击中音效
                             let hit_sound: Handle<AudioSource> = asset_server.load(SOUND_HIT);
@@ -375,7 +514,7 @@ pub fn bullet_terrain_collision_system(
                             });
 
                             // 只标记子弹销毁
-                            commands.entity(bullet_entity).try_insert(BulletDespawnMarker);
+                            commands.entity(bullet_entity).try_insert((BulletDespawnMarker, BulletImpactMarker));
                         }
                     }
                 } else {
@@ -389,7 +528,7 @@ pub fn bullet_terrain_collision_system(
                     });
 
                     // 只标记子弹销毁
-                    commands.entity(bullet_entity).try_insert(BulletDespawnMarker);
+                    commands.entity(bullet_entity).try_insert((BulletDespawnMarker, BulletImpactMarker));
                 }
             }
         }
@@ -400,18 +539,15 @@ pub fn bullet_terrain_collision_system(
 pub fn bullet_tank_collision_system(
     mut commands: Commands,
     mut collision_events: MessageReader<CollisionEvent>,
-    mut effect_events: MessageWriter<EffectEvent>,
-    _texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
-    asset_server: Res<AssetServer>,
     bullets: Query<(Entity, &BulletOwner, &Transform), With<Bullet>>,
     enemy_tanks: Query<(), With<EnemyTank>>,
-    enemy_tanks_with_transform: Query<(Entity, &Transform), With<EnemyTank>>,
     player_tanks: Query<&PlayerTank, With<PlayerTank>>,
-    player_tanks_with_transform: Query<(Entity, &Transform), With<PlayerTank>>,
-    player_avatars: Query<(Entity, &PlayerUI)>,
-    mut player_info: ResMut<PlayerInfo>,
-    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
-    mut controllers: Query<&mut KinematicCharacterController>,
+    bullet_tracker: Res<BulletTracker>,
+    match_options: Res<MatchOptions>,
+    game_mode: Res<GameMode>,
+    missiles: Query<(), With<crate::missile::Missile>>,
+    mut stage_stats: ResMut<StageStats>,
+    mut damage_events: MessageWriter<Damage>,
 ) {
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
@@ -424,139 +560,61 @@ pub fn bullet_tank_collision_system(
                 &player_tanks,
             ) {
                 let bullet_owner_info = bullets.get(bullet_entity).unwrap().1;
+                let shooter = bullet_tracker.bullet_to_tank.get(&bullet_entity).copied();
 
-                if should_bullet_destroy(bullet_owner_info.owner_type, tank_entity, &enemy_tanks, &player_tanks) {
-                    // 检查是否是玩家子弹击中敌方坦克
-                    let is_player_bullet = matches!(bullet_owner_info.owner_type, TankType::Player1 | TankType::Player2);
-                    let is_enemy_tank = enemy_tanks.get(tank_entity).is_ok();
-                    let is_player_tank = player_tanks.get(tank_entity).is_ok();
-
-                    if is_player_bullet && is_enemy_tank {
-                        // 获取敌方坦克的位置
-                        if let Ok((_, tank_transform)) = enemy_tanks_with_transform.get(tank_entity) {
-                            // 发送爆炸特效事件
-                            effect_events.write(EffectEvent::Explosion {
-                                position: tank_transform.translation,
-                            });
-                        }
+                if should_bullet_destroy(bullet_owner_info.owner_type, shooter, tank_entity, &enemy_tanks, &player_tanks, &match_options, *game_mode) {
+                    // 统计玩家炮弹（不含导弹）直接命中敌方坦克的次数
+                    let owner_type = bullet_owner_info.owner_type;
+                    if owner_type != TankType::Enemy && enemy_tanks.contains(tank_entity) && !missiles.contains(bullet_entity) {
+                        stage_stats.players.entry(owner_type).or_default().shots_hit += 1;
+                    }
 
-                        // 销毁敌方坦克
-                        let _ = commands.entity(tank_entity).try_despawn();
+                    // 被击中的坦克受到伤害（特效抵挡、击毁和计分统一结算）
+                    damage_events.write(Damage {
+                        target: tank_entity,
+                        amount: 1,
+                        kind: DamageKind::Shell,
+                        source: Some(bullet_owner_info.owner_type),
+                    });
+                    // 标记子弹销毁
+                    commands.entity(bullet_entity).try_insert((BulletDespawnMarker, BulletImpactMarker));
+                }
+            }
+        }
+    }
+}
 
-                        // 增加分数
-                        let player_type = bullet_owner_info.owner_type;
-                        if player_type == TankType::Enemy {
-                            continue; // 敌方坦克不应该有这个分支
-                        }
-                        if let Some(player_stats) = player_info.players.get_mut(&player_type) {
-                            player_stats.score += 100;
+/// 子弹与子弹碰撞检测系统
+/// 玩家子弹与敌方子弹相撞时互相抵消，并在相撞处产生火花（对战模式下两名玩家的子弹也互相抵消）
+pub fn bullet_bullet_collision_system(
+    mut commands: Commands,
+    mut collision_events: MessageReader<CollisionEvent>,
+    mut effect_events: MessageWriter<EffectEvent>,
+    game_mode: Res<GameMode>,
+    bullets: Query<(&BulletOwner, &Transform), (With<Bullet>, Without<BulletDespawnMarker>)>,
+) {
+    for event in collision_events.read() {
+        if let CollisionEvent::Started(e1, e2, _) = event {
+            let (Ok((owner1, transform1)), Ok((owner2, transform2))) = (bullets.get(*e1), bullets.get(*e2)) else {
+                continue;
+            };
 
-                            // 发送分数变更事件
-                            stat_changed_events.write(PlayerStatChanged {
-                                player_type: player_type,
-                                stat_type: StatType::Score,
-                            });
-                        }
-                    } else if !is_player_bullet && is_player_tank {
-                        let player_index = match player_tanks.get(tank_entity) {
-                            Ok(pt) => pt.tank_type,
-                            Err(_) => continue,
-                        };
-
-                        // 敌方子弹击中玩家坦克
-                        // 播放中弹音效
-                        let hit_sound: Handle<AudioSource> = asset_server.load(SOUND_HIT);
-                        commands.spawn(AudioPlayer::new(hit_sound));
+            // 只有敌我双方的子弹会互相抵消，同一方的子弹互相穿过（对战模式下两名玩家互为敌方）
+            let is_player_bullet1 = matches!(owner1.owner_type, TankType::Player1 | TankType::Player2);
+            let is_player_bullet2 = matches!(owner2.owner_type, TankType::Player1 | TankType::Player2);
+            let is_versus_duel = *game_mode == GameMode::Versus && is_player_bullet1 && is_player_bullet2 && owner1.owner_type != owner2.owner_type;
+            if is_player_bullet1 == is_player_bullet2 && !is_versus_duel {
+                continue;
+            }
 
-                        // 发送火花特效事件
-                        if let Ok((_, tank_transform)) = player_tanks_with_transform.get(tank_entity) {
-                            effect_events.write(EffectEvent::Spark {
-                                position: tank_transform.translation,
-                            });
-                        }
+            // 发送火花特效事件
+            effect_events.write(EffectEvent::Spark {
+                position: (transform1.translation + transform2.translation) / 2.0,
+            });
 
-                        // 扣除对应玩家的生命值
-                        if let Some(player_stats) = player_info.players.get_mut(&player_index) {
-                            // 检查玩家是否有 fire_shell、track_chain、penetrate 或 air_cushion 特效
-                            let has_fire_shell = player_stats.fire_shell;
-                            let has_track_chain = player_stats.track_chain;
-                            let has_penetrate = player_stats.penetrate;
-                            let has_air_cushion = player_stats.air_cushion;
-                            let has_shells = player_stats.shells > 1;
-
-                            if has_fire_shell || has_track_chain || has_penetrate || has_air_cushion || has_shells {
-                                // 有特效或额外子弹，移除其中一个（优先级任意）
-                                if has_fire_shell {
-                                    player_stats.fire_shell = false;
-                                    stat_changed_events.write(PlayerStatChanged {
-                                        player_type: player_index,
-                                        stat_type: StatType::FireShell,
-                                    });
-                                } else if has_track_chain {
-                                    player_stats.track_chain = false;
-                                    stat_changed_events.write(PlayerStatChanged {
-                                        player_type: player_index,
-                                        stat_type: StatType::TrackChain,
-                                    });
-                                } else if has_penetrate {
-                                    player_stats.penetrate = false;
-                                    stat_changed_events.write(PlayerStatChanged {
-                                        player_type: player_index,
-                                        stat_type: StatType::Penetrate,
-                                    });
-                                } else if has_air_cushion {
-                                    player_stats.air_cushion = false;
-                                    // 恢复 filter_groups，与海（GROUP_2）碰撞
-                                    if let Ok(mut controller) = controllers.get_mut(tank_entity) {
-                                        controller.filter_groups = None;
-                                    }
-                                    // 移除气泡特效标记
-                                    commands.entity(tank_entity).remove::<crate::constants::BubbleEffect>();
-                                    stat_changed_events.write(PlayerStatChanged {
-                                        player_type: player_index,
-                                        stat_type: StatType::AirCushion,
-                                    });
-                                } else if has_shells {
-                                    // 扣减 1 颗子弹
-                                    player_stats.shells -= 1;
-                                    stat_changed_events.write(PlayerStatChanged {
-                                        player_type: player_index,
-                                        stat_type: StatType::Shell,
-                                    });
-                                }
-                            } else {
-                                // 没有特效，正常扣血
-                                if player_stats.life_red_bar > 0 {
-                                    player_stats.life_red_bar -= 1;
-                                }
-                                if player_stats.life_red_bar == 0 {
-                                    // 获取玩家坦克的位置
-                                    if let Ok((_, tank_transform)) =
-                                        player_tanks_with_transform.get(tank_entity)
-                                    {
-                                        // 发送爆炸特效事件
-                                        effect_events.write(EffectEvent::Explosion {
-                                            position: tank_transform.translation,
-                                        });
-                                    }
-
-                                    // 销毁玩家坦克
-                                    let _ = commands.entity(tank_entity).try_despawn();
-
-                                    // 标记对应玩家的头像为死亡状态
-                                    for (avatar_entity, player_idx) in player_avatars.iter() {
-                                        if player_idx.player_type == player_index {
-                                            commands.entity(avatar_entity).insert(PlayerDead);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    // 标记子弹销毁
-                    commands.entity(bullet_entity).try_insert(BulletDespawnMarker);
-                }
-            }
+            // 标记两颗子弹销毁（由统一销毁系统归还双方的射击次数）
+            commands.entity(*e1).try_insert(BulletDespawnMarker);
+            commands.entity(*e2).try_insert(BulletDespawnMarker);
         }
     }
 }
@@ -584,16 +642,14 @@ pub fn handle_effect_events(
     }
 }
 
-/// 司令官与敌方子弹碰撞检测系统
+/// 司令官与敌方子弹碰撞检测系统（对战模式下玩家的子弹也会击中对方的司令官）
 /// 使用简单的 AABB 碰撞检测
 pub fn bullet_commander_collision_system(
     mut commands: Commands,
     mut collision_events: MessageReader<CollisionEvent>,
-    mut effect_events: MessageWriter<EffectEvent>,
-    asset_server: Res<AssetServer>,
     bullets: Query<(Entity, &BulletOwner, &Transform), With<Bullet>>,
-    commanders: Query<(Entity, &Transform), With<crate::constants::Commander>>,
-    mut commander_life: ResMut<crate::resources::CommanderLife>,
+    commanders: Query<(Entity, &Transform, Option<&CommanderOwner>), With<crate::constants::Commander>>,
+    mut damage_events: MessageWriter<Damage>,
 ) {
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
@@ -612,17 +668,18 @@ pub fn bullet_commander_collision_system(
                 Err(_) => continue,
             };
 
-            // 只处理敌方子弹击中司令官的情况
-            if bullet_owner_info.owner_type != TankType::Enemy {
+            // 获取司令官位置和所属玩家
+            let Ok((_, commander_transform, commander_owner)) = commanders.get(commander_entity) else {
                 continue;
-            }
-
-            // 获取司令官位置
-            let commander_transform = match commanders.get(commander_entity) {
-                Ok((_, transform)) => transform,
-                Err(_) => continue,
             };
 
+            // 只处理敌方子弹或对方玩家的子弹击中司令官的情况
+            let owner_type = bullet_owner_info.owner_type;
+            let is_opponent_shell = commander_owner.is_some_and(|owner| owner.0 != owner_type);
+            if owner_type != TankType::Enemy && !is_opponent_shell {
+                continue;
+            }
+
             // 使用 AABB 碰撞检测验证碰撞
             let bullet_transform = match bullets.get(bullet_entity) {
                 Ok((_, _, transform)) => transform,
@@ -643,29 +700,16 @@ pub fn bullet_commander_collision_system(
                 && commander_min.y <= bullet_max.y
                 && commander_max.y >= bullet_min.y
             {
-                // 碰撞确认，播放受击音效
-                let hit_sound: Handle<AudioSource> = asset_server.load(SOUND_COMMANDER_GET_SHOT);
-                commands.spawn(AudioPlayer::new(hit_sound));
-
-                // 发送火花特效事件
-                effect_events.write(EffectEvent::Spark {
-                    position: bullet_transform.translation,
+                // 碰撞确认，司令官受到伤害（音效、特效和生命值统一结算）
+                damage_events.write(Damage {
+                    target: commander_entity,
+                    amount: 1,
+                    kind: DamageKind::Shell,
+                    source: Some(bullet_owner_info.owner_type),
                 });
 
-                // 减少司令官生命值
-                if commander_life.life_red_bar > 0 {
-                    commander_life.life_red_bar -= 1;
-                }
-
-                // 检查是否是致命伤（生命值归零）
-                if commander_life.life_red_bar == 0 {
-                    // 播放死亡音效
-                    let death_sound: Handle<AudioSource> = asset_server.load(SOUND_COMMANDER_DEATH);
-                    commands.spawn(AudioPlayer::new(death_sound));
-                }
-
                 // 标记子弹销毁
-                commands.entity(bullet_entity).try_insert(BulletDespawnMarker);
+                commands.entity(bullet_entity).try_insert((BulletDespawnMarker, BulletImpactMarker));
             }
         }
     }
@@ -0,0 +1,406 @@
+//! 战斗结算模块
+//!
+//! 统一处理所有坦克和司令官的伤害结算（护甲、特效抵挡和死亡）、敌方坦克被击毁后的结算（销毁实体、爆炸特效和计分），以及爆炸弹的范围伤害
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::collections::HashSet;
+
+use crate::bullet::{EffectEvent, ShellExploded};
+use crate::constants::{
+    Armor, Brick, Commander, CommanderOwner, EnemyClass, EnemyTank, Health, Invulnerable,
+    PlayerAvatar, PlayerDead, PlayerTank, PlayerUI, RecoilForce, SOUND_COMMANDER_DEATH,
+    SOUND_COMMANDER_GET_SHOT, SOUND_HIT, SPLASH_BRICK_RADIUS, SPLASH_PUSH_DISTANCE,
+    SPLASH_TANK_RADIUS, TankType,
+};
+use crate::resources::{
+    CommanderLife, GameMode, MatchOptions, PlayerInfo, PlayerStatChanged, PlayerStats, StageStats,
+    StatType,
+};
+
+/// 误伤击毁队友扣除的分数
+pub const TEAM_KILL_PENALTY: usize = 500;
+/// 每多少点防护抵消 1 点撞击伤害（<40% 全额，40%~80% 减 1，≥80% 减 2）
+pub const ARMOR_PER_DAMAGE_POINT: usize = 40;
+/// 每点屏障伤害永久降低的速度和防护
+pub const BARRIER_STAT_PENALTY: usize = 20;
+
+/// 敌方坦克被击毁事件
+/// 伤害结算系统在敌方坦克生命值归零时发送，统一处理销毁和计分
+#[derive(Message, Clone, Copy)]
+pub struct EnemyKilled {
+    pub entity: Entity,
+    pub position: Vec3,
+    pub killer: Option<TankType>, // 击毁者，None 表示不计分
+    pub class: EnemyClass,
+    pub cause: DamageKind, // 致命伤害的类型（关卡结算统计激光击毁数）
+}
+
+/// 伤害类型，决定特效抵挡、护甲减免和免疫规则
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DamageKind {
+    Shell,     // 炮弹直接命中
+    Explosion, // 爆炸弹的范围伤害
+    Laser,     // 激光
+    Mine,      // 地雷（履带链免疫）
+    Ram,       // 冲刺撞击砖块或坦克，由护甲减免
+    Crush,     // 冲刺撞上钢铁（防护不足 100%），直接摧毁（忽略伤害值）
+    Grenade,   // 手雷道具，直接摧毁（忽略伤害值）
+    Barrier,   // 屏障：不扣生命值，每点伤害永久降低速度和防护（履带链免疫）
+}
+
+/// 伤害事件
+/// 子弹、爆炸、激光、地雷、冲刺、屏障和手雷对坦克和司令官造成的伤害都通过它统一结算
+#[derive(Message, Clone, Copy)]
+pub struct Damage {
+    pub target: Entity,
+    pub amount: usize,
+    pub kind: DamageKind,
+    pub source: Option<TankType>, // 伤害来源，None 表示地形等非坦克来源
+}
+
+/// 敌方坦克击毁结算系统
+pub fn resolve_enemy_kills(
+    mut commands: Commands,
+    mut events: MessageReader<EnemyKilled>,
+    mut effect_events: MessageWriter<EffectEvent>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+    mut stage_stats: ResMut<StageStats>,
+    enemy_tanks: Query<(), With<EnemyTank>>,
+) {
+    // 同一帧内同一辆坦克可能被多种途径同时击中，只结算一次
+    let mut resolved = HashSet::new();
+
+    for event in events.read() {
+        // 已经在之前的帧结算过的坦克不再重复计分
+        if !resolved.insert(event.entity) || !enemy_tanks.contains(event.entity) {
+            continue;
+        }
+
+        // 发送爆炸特效事件
+        effect_events.write(EffectEvent::Explosion {
+            position: event.position,
+        });
+
+        // 销毁敌方坦克
+        commands.entity(event.entity).try_despawn();
+
+        // 增加击毁者分数
+        let Some(player_type) = event.killer else {
+            continue;
+        };
+        // 记录本关击毁统计
+        let player_stage_stats = stage_stats.players.entry(player_type).or_default();
+        *player_stage_stats.kills.entry(event.class).or_default() += 1;
+        if event.cause == DamageKind::Laser {
+            player_stage_stats.laser_kills += 1;
+        }
+
+        if let Some(player_stats) = player_info.players.get_mut(&player_type) {
+            player_stats.score += event.class.score();
+
+            // 发送分数变更事件
+            stat_changed_events.write(PlayerStatChanged {
+                player_type,
+                stat_type: StatType::Score,
+            });
+        }
+    }
+}
+
+/// 生命值同步系统
+/// 修理、汉堡道具和限时增益直接修改 `PlayerStats` / `CommanderLife`，结算伤害前同步到 Health 和 Armor 组件
+/// （对战模式中各自所属的司令官生命值只保存在 Health 组件里）
+pub fn sync_health_components(
+    player_info: Res<PlayerInfo>,
+    commander_life: Res<CommanderLife>,
+    mut targets: Query<(&mut Health, &mut Armor, Option<&PlayerTank>, Has<Commander>, Has<CommanderOwner>)>,
+) {
+    for (mut health, mut armor, player_tank, is_commander, has_owner) in &mut targets {
+        if let Some(player_tank) = player_tank {
+            if let Some(player_stats) = player_info.players.get(&player_tank.tank_type) {
+                health.current = player_stats.life_red_bar.min(health.max);
+                armor.0 = player_stats.protection;
+            }
+        } else if is_commander && !has_owner {
+            health.current = commander_life.life_red_bar.min(health.max);
+        }
+    }
+}
+
+/// 玩家的特效或额外子弹抵挡一次伤害，返回被消耗的属性
+fn absorb_with_shield(
+    commands: &mut Commands,
+    player_stats: &mut PlayerStats,
+    controller: Option<Mut<KinematicCharacterController>>,
+    tank_entity: Entity,
+) -> Option<StatType> {
+    // 有特效或额外子弹，移除其中一个（优先级任意）
+    if player_stats.fire_shell {
+        player_stats.fire_shell = false;
+        crate::buff::discard_buff(player_stats, StatType::FireShell, 0);
+        Some(StatType::FireShell)
+    } else if player_stats.track_chain {
+        player_stats.track_chain = false;
+        crate::buff::discard_buff(player_stats, StatType::TrackChain, 0);
+        Some(StatType::TrackChain)
+    } else if player_stats.penetrate {
+        player_stats.penetrate = false;
+        crate::buff::discard_buff(player_stats, StatType::Penetrate, 0);
+        Some(StatType::Penetrate)
+    } else if player_stats.air_cushion {
+        player_stats.air_cushion = false;
+        crate::buff::discard_buff(player_stats, StatType::AirCushion, 0);
+        // 恢复 filter_groups，与海（GROUP_2）碰撞，并移除气泡特效
+        crate::buff::set_air_cushion(commands, controller, tank_entity, false);
+        Some(StatType::AirCushion)
+    } else if player_stats.shells > 1 {
+        // 扣减 1 颗子弹
+        player_stats.shells -= 1;
+        crate::buff::discard_buff(player_stats, StatType::Shell, 1);
+        Some(StatType::Shell)
+    } else {
+        None
+    }
+}
+
+/// 伤害结算系统
+/// 依次处理无敌、免疫、特效抵挡和护甲减免，扣除生命值后同步到 HUD 使用的资源，生命值归零时摧毁目标
+pub fn resolve_damage(
+    mut commands: Commands,
+    mut events: MessageReader<Damage>,
+    mut effect_events: MessageWriter<EffectEvent>,
+    mut enemy_killed_events: MessageWriter<EnemyKilled>,
+    asset_server: Res<AssetServer>,
+    mut targets: Query<(&mut Health, &mut Armor, &Transform, Option<&PlayerTank>, Has<EnemyTank>, Has<Commander>, Has<CommanderOwner>, Has<Invulnerable>)>,
+    enemy_classes: Query<&EnemyClass>,
+    player_avatars: Query<(Entity, &PlayerUI), With<PlayerAvatar>>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut commander_life: ResMut<CommanderLife>,
+    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+    mut controllers: Query<&mut KinematicCharacterController>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    game_mode: Res<GameMode>,
+) {
+    for event in events.read() {
+        let Ok((mut health, mut armor, transform, player_tank, is_enemy_tank, is_commander, has_owner, is_invulnerable)) = targets.get_mut(event.target) else {
+            continue;
+        };
+        // 同一帧内已被摧毁的目标不再重复结算，复活后无敌的玩家不受任何伤害
+        if health.current == 0 || is_invulnerable {
+            continue;
+        }
+        let position = transform.translation;
+        let target_transform = *transform;
+        let player_type = player_tank.map(|player_tank| player_tank.tank_type);
+
+        if let Some(player_type) = player_type {
+            let Some(player_stats) = player_info.players.get_mut(&player_type) else {
+                continue;
+            };
+
+            // 拥有履带链时免疫屏障和地雷
+            if matches!(event.kind, DamageKind::Barrier | DamageKind::Mine) && player_stats.track_chain {
+                continue;
+            }
+
+            match event.kind {
+                DamageKind::Barrier => {
+                    // 屏障不扣生命值，永久降低速度和防护
+                    let penalty = BARRIER_STAT_PENALTY * event.amount;
+                    player_stats.speed = player_stats.speed.saturating_sub(penalty);
+                    player_stats.protection = player_stats.protection.saturating_sub(penalty);
+                    armor.0 = player_stats.protection;
+
+                    // 发送 speed 和 protection 变更事件
+                    stat_changed_events.write(PlayerStatChanged {
+                        player_type,
+                        stat_type: StatType::Speed,
+                    });
+                    stat_changed_events.write(PlayerStatChanged {
+                        player_type,
+                        stat_type: StatType::Protection,
+                    });
+                    continue;
+                }
+                DamageKind::Shell | DamageKind::Explosion | DamageKind::Laser | DamageKind::Mine => {
+                    // 播放中弹音效
+                    let hit_sound: Handle<AudioSource> = asset_server.load(SOUND_HIT);
+                    commands.spawn(AudioPlayer::new(hit_sound));
+
+                    // 发送火花特效事件
+                    effect_events.write(EffectEvent::Spark { position });
+
+                    // 特效或额外子弹抵挡一次伤害
+                    let controller = controllers.get_mut(event.target).ok();
+                    if let Some(stat_type) = absorb_with_shield(&mut commands, player_stats, controller, event.target) {
+                        stat_changed_events.write(PlayerStatChanged { player_type, stat_type });
+                        continue;
+                    }
+                }
+                DamageKind::Ram | DamageKind::Crush | DamageKind::Grenade => {}
+            }
+        } else if is_commander {
+            // 播放司令官受击音效
+            let hit_sound: Handle<AudioSource> = asset_server.load(SOUND_COMMANDER_GET_SHOT);
+            commands.spawn(AudioPlayer::new(hit_sound));
+
+            // 发送火花特效事件
+            effect_events.write(EffectEvent::Spark { position });
+        }
+
+        // 护甲只减免撞击伤害，钢铁和手雷直接摧毁目标
+        let amount = match event.kind {
+            DamageKind::Ram => event.amount.saturating_sub(armor.0 / ARMOR_PER_DAMAGE_POINT),
+            DamageKind::Crush | DamageKind::Grenade => health.current,
+            _ => event.amount,
+        };
+        if amount == 0 {
+            continue;
+        }
+        health.current = health.current.saturating_sub(amount);
+
+        // 同步到 HUD 使用的资源
+        if let Some(player_type) = player_type
+            && let Some(player_stats) = player_info.players.get_mut(&player_type)
+        {
+            player_stats.life_red_bar = health.current;
+        } else if is_commander && !has_owner {
+            commander_life.life_red_bar = health.current;
+        }
+
+        if health.current > 0 {
+            // 重甲敌方坦克被击中但未被击毁，播放中弹音效和火花
+            if is_enemy_tank {
+                let hit_sound: Handle<AudioSource> = asset_server.load(SOUND_HIT);
+                commands.spawn(AudioPlayer::new(hit_sound));
+                effect_events.write(EffectEvent::Spark { position });
+            }
+            continue;
+        }
+
+        if is_enemy_tank {
+            // 敌方坦克被击毁（敌方误伤自己人不计分）
+            enemy_killed_events.write(EnemyKilled {
+                entity: event.target,
+                position,
+                killer: event.source.filter(|source| *source != TankType::Enemy),
+                class: enemy_classes.get(event.target).copied().unwrap_or_default(),
+                cause: event.kind,
+            });
+        } else if is_commander {
+            // 播放司令官死亡音效
+            let death_sound: Handle<AudioSource> = asset_server.load(SOUND_COMMANDER_DEATH);
+            commands.spawn(AudioPlayer::new(death_sound));
+        } else if let Some(player_type) = player_type {
+            // 发送爆炸特效事件
+            effect_events.write(EffectEvent::Explosion { position });
+
+            // 销毁玩家坦克，原地留下残骸
+            commands.entity(event.target).try_despawn();
+            crate::revive::spawn_player_wreck(&mut commands, &asset_server, &mut texture_atlas_layouts, player_type, &target_transform);
+
+            // 标记对应玩家的头像为死亡状态
+            for (avatar_entity, player_idx) in player_avatars.iter() {
+                if player_idx.player_type == player_type {
+                    commands.entity(avatar_entity).insert(PlayerDead);
+                }
+            }
+
+            // 误伤击毁队友，扣除攻击方分数（对战模式击毁对手不扣分）
+            let Some(attacker) = event.source else {
+                continue;
+            };
+            if *game_mode == GameMode::Versus {
+                continue;
+            }
+            let is_team_kill = matches!(attacker, TankType::Player1 | TankType::Player2) && attacker != player_type;
+            if is_team_kill && let Some(attacker_stats) = player_info.players.get_mut(&attacker) {
+                attacker_stats.score = attacker_stats.score.saturating_sub(TEAM_KILL_PENALTY);
+                stat_changed_events.write(PlayerStatChanged {
+                    player_type: attacker,
+                    stat_type: StatType::Score,
+                });
+            }
+        }
+    }
+}
+
+/// 爆炸弹伤害随距离衰减：中心一半范围内 2 点，其余范围内 1 点
+fn splash_damage(distance: f32) -> usize {
+    if distance <= SPLASH_TANK_RADIUS / 2.0 {
+        2
+    } else {
+        usize::from(distance <= SPLASH_TANK_RADIUS)
+    }
+}
+
+/// 爆炸弹范围伤害系统：摧毁附近砖块，伤害并推开附近坦克
+pub fn resolve_shell_explosions(
+    mut commands: Commands,
+    mut events: MessageReader<ShellExploded>,
+    mut effect_events: MessageWriter<EffectEvent>,
+    mut damage_events: MessageWriter<Damage>,
+    bricks: Query<(Entity, &Transform), With<Brick>>,
+    tanks: Query<(Entity, &Transform, Has<EnemyTank>, Option<&PlayerTank>), Or<(With<EnemyTank>, With<PlayerTank>)>>,
+    match_options: Res<MatchOptions>,
+    game_mode: Res<GameMode>,
+) {
+    for event in events.read() {
+        // 发送爆炸特效事件
+        effect_events.write(EffectEvent::Explosion {
+            position: event.position,
+        });
+
+        // 摧毁爆炸范围内的砖块
+        for (brick_entity, brick_transform) in &bricks {
+            if brick_transform.translation.truncate().distance(event.position.truncate()) <= SPLASH_BRICK_RADIUS {
+                commands.entity(brick_entity).try_despawn();
+            }
+        }
+
+        let is_player_shell = matches!(event.owner_type, TankType::Player1 | TankType::Player2);
+        for (tank_entity, tank_transform, is_enemy_tank, player_tank) in &tanks {
+            let offset = (tank_transform.translation - event.position).truncate();
+            let damage = splash_damage(offset.length());
+            if damage == 0 {
+                continue;
+            }
+
+            // 玩家的爆炸伤害敌方坦克（重甲坦克可能挺过爆炸，和其他坦克一样被推开）
+            if is_enemy_tank && is_player_shell {
+                damage_events.write(Damage {
+                    target: tank_entity,
+                    amount: damage,
+                    kind: DamageKind::Explosion,
+                    source: Some(event.owner_type),
+                });
+            }
+
+            // 开启友军误伤或对战模式时，爆炸也会伤害另一名玩家（不会伤害发射者自己）
+            if let Some(player_tank) = player_tank
+                && is_player_shell
+                && match_options.players_can_hurt_each_other(*game_mode)
+                && player_tank.tank_type != event.owner_type
+            {
+                damage_events.write(Damage {
+                    target: tank_entity,
+                    amount: damage,
+                    kind: DamageKind::Explosion,
+                    source: Some(event.owner_type),
+                });
+            }
+
+            // 未被击毁的坦克被冲击波推开，越靠近中心推得越远
+            let falloff = 1.0 - offset.length() / SPLASH_TANK_RADIUS;
+            let push = offset.normalize_or_zero() * SPLASH_PUSH_DISTANCE * falloff;
+            // 后坐力从原位插值到目标位置：起点为当前位置，终点为推开后的位置
+            commands.entity(tank_entity).try_insert(RecoilForce {
+                original_pos: tank_transform.translation + push.extend(0.0),
+                target_offset: -push,
+                timer: Timer::from_seconds(0.2, TimerMode::Once),
+            });
+        }
+    }
+}
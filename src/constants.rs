@@ -52,6 +52,7 @@ pub const TEXTURE_PLAYER_TANK1: &str = "texture/player_tank1_sprite.png";
 pub const TEXTURE_PLAYER_TANK2: &str = "texture/player_tank2_sprite.png";
 pub const TEXTURE_AVATAR_DEATH: &str = "texture/avatar_death.png";
 pub const TEXTURE_AVATAR_COMMANDER_DEAD: &str = "texture/avatar_commander_dead.png";
+pub const TEXTURE_MINE: &str = "texture/mine.png";
 
 // 碰撞分组常量
 pub const SEA_GROUP: Group = Group::GROUP_2;
@@ -71,6 +72,26 @@ pub const PLAYER_TANK_SPEED: f32 = 150.0;
 pub const BULLET_SPEED: f32 = 900.0;
 pub const PLAYER_BULLET_SPEED: f32 = 600.0;
 pub const BULLET_SIZE: f32 = 10.0;
+pub const RICOCHET_MAX_BOUNCES: u32 = 3; // 跳弹最多反弹次数
+pub const RICOCHET_SPEED_FACTOR: f32 = 0.8; // 每次反弹后保留的速度比例
+pub const SPLASH_BRICK_RADIUS: f32 = 75.0; // 爆炸弹摧毁砖块的半径
+pub const SPLASH_TANK_RADIUS: f32 = 120.0; // 爆炸弹伤害坦克的半径（中心一半范围内伤害加倍）
+pub const SPLASH_PUSH_DISTANCE: f32 = 40.0; // 爆炸把坦克推开的最大距离
+pub const MISSILE_SPEED: f32 = 450.0; // 追踪导弹飞行速度
+pub const MISSILE_TURN_RATE: f32 = 2.5; // 追踪导弹每秒最多转向的弧度
+pub const MISSILE_LIFETIME: f32 = 4.0; // 追踪导弹超时自爆的时间（秒）
+pub const MISSILE_START_AMMO: usize = 2; // 每关开始时的导弹数量
+pub const STARTING_LIVES: usize = 2; // 新游戏开始时的备用生命数
+pub const MAX_LIVES: usize = 9; // 备用生命数上限
+pub const MISSILE_MAX_AMMO: usize = 5; // 导弹数量上限
+pub const MISSILE_AMMO_PER_PICKUP: usize = 2; // 每个导弹道具补充的数量
+pub const MINE_SIZE: f32 = 50.0; // 地雷尺寸（传感器直径）
+pub const MINE_ARM_DELAY: f32 = 1.5; // 地雷放下后激活的延时（秒）
+pub const MINE_MAX_ACTIVE: usize = 3; // 每名玩家同时存在的地雷上限
+pub const BUILD_ENERGY_COST: usize = 1; // 砌砖消耗的蓝条
+pub const LASER_LENGTH: f32 = 1366.0; // 激光束长度
+pub const LASER_HALF_WIDTH: f32 = 35.0; // 激光束命中判定的半宽（贴图两侧是透明的）
+pub const LASER_STOPPED_BY_STEEL: bool = true; // 钢铁是否挡住激光（有穿透效果时不受影响）
 pub const RECALL_TIME: f32 = 4.0; // 回城时间（秒）
 pub const VERTICAL_OFFSET: f32 = (BOTTOM_PADDING-TOP_PADDING) / 2.0; // 由于下边不留白，会导致坐标垂直便移-50
 pub const WINDOW_WIDTH: f32 = MAP_WIDTH + LEFT_PADDING + RIGHT_PADDING; // 总宽度
@@ -97,6 +118,17 @@ pub const START_SCREEN_BACKGROUND_COLOR: Color = Color::srgb(17.0/255.0, 81.0/25
 pub const COMMANDER_WIDTH: f32 = 100.0;
 pub const COMMANDER_HEIGHT: f32 = 100.0;
 
+// 玩家出生位置（司令官两侧）
+pub const PLAYER1_START_POSITION: Vec3 = Vec3::new(-TANK_WIDTH / 2.0 - COMMANDER_WIDTH / 2.0 - 50.0, MAP_BOTTOM_Y + TANK_HEIGHT / 2.0, 0.0);
+pub const PLAYER2_START_POSITION: Vec3 = Vec3::new(TANK_WIDTH / 2.0 + COMMANDER_WIDTH / 2.0 + 50.0, MAP_BOTTOM_Y + TANK_HEIGHT / 2.0, 0.0);
+
+// 对战模式：司令官分别在地图左右两端的中间，玩家出生在自己的堡垒上方
+pub const MAP_CENTER_Y: f32 = f32::midpoint(MAP_TOP_Y, MAP_BOTTOM_Y);
+pub const VERSUS_COMMANDER1_POSITION: Vec3 = Vec3::new(MAP_LEFT_X + COMMANDER_WIDTH / 2.0, MAP_CENTER_Y, 0.0);
+pub const VERSUS_COMMANDER2_POSITION: Vec3 = Vec3::new(MAP_RIGHT_X - COMMANDER_WIDTH / 2.0, MAP_CENTER_Y, 0.0);
+pub const VERSUS_PLAYER1_START_POSITION: Vec3 = Vec3::new(MAP_LEFT_X + TANK_WIDTH / 2.0, MAP_CENTER_Y + COMMANDER_HEIGHT / 2.0 + 50.0 + TANK_HEIGHT / 2.0, 0.0);
+pub const VERSUS_PLAYER2_START_POSITION: Vec3 = Vec3::new(MAP_RIGHT_X - TANK_WIDTH / 2.0, MAP_CENTER_Y + COMMANDER_HEIGHT / 2.0 + 50.0 + TANK_HEIGHT / 2.0, 0.0);
+
 pub const DIRECTIONS: [Vec2; 4] = [
     Vec2::new(0.0, 1.0),   // 上
     Vec2::new(0.0, -1.0),  // 下
@@ -107,6 +139,7 @@ pub const DIRECTIONS: [Vec2; 4] = [
 // 冲刺相关常量
 pub const DASH_DURATION: f32 = 0.2; // 冲刺持续时间（秒）
 pub const DASH_DISTANCE: f32 = TANK_HEIGHT * 2.0; // 冲刺距离（两个坦克长度）
+pub const DASH_RAM_DAMAGE: usize = 2; // 冲刺撞击对自己造成的伤害（由护甲减免）
 
 // 关卡俏皮话，根据关卡序号选择（索引从0开始）
 pub const STAGE_QUOTES: [&str; 17] = [
@@ -223,6 +256,13 @@ pub const PLAYER1_UI_ELEMENTS: &[UIElementConfig] = &[
         y_pos: WINDOW_TOP_Y - 980.0,
         font_size: 24.0,
     },
+    // Lives
+    UIElementConfig {
+        element_type: UIElementType::NormalText(|info| format!("Lives: {}", info.lives)),
+        x_pos: WINDOW_LEFT_X + 115.0,
+        y_pos: WINDOW_TOP_Y - 1030.0,
+        font_size: 24.0,
+    },
     // Penetrate
     UIElementConfig {
         element_type: UIElementType::NormalText(|info| format!("Penetrate: {}", if info.penetrate { "On" } else { "Off" })),
@@ -244,6 +284,20 @@ pub const PLAYER1_UI_ELEMENTS: &[UIElementConfig] = &[
         y_pos: WINDOW_TOP_Y - 520.0,
         font_size: 24.0,
     },
+    // Ricochet
+    UIElementConfig {
+        element_type: UIElementType::NormalText(|info| format!("Ricochet:{}", if info.ricochet { "On" } else { "Off" })),
+        x_pos: WINDOW_LEFT_X + 115.0,
+        y_pos: WINDOW_TOP_Y - 570.0,
+        font_size: 24.0,
+    },
+    // Missiles
+    UIElementConfig {
+        element_type: UIElementType::NormalText(|info| format!("Missiles: {}", info.missiles)),
+        x_pos: WINDOW_LEFT_X + 115.0,
+        y_pos: WINDOW_TOP_Y - 620.0,
+        font_size: 24.0,
+    },
     // Fire Shell
     UIElementConfig {
         element_type: UIElementType::NormalText(|info| format!("Fire Shell:{}", if info.fire_shell { "On" } else { "Off" })),
@@ -343,6 +397,13 @@ pub const PLAYER2_UI_ELEMENTS: &[UIElementConfig] = &[
         y_pos: WINDOW_TOP_Y - 980.0,
         font_size: 24.0,
     },
+    // Lives
+    UIElementConfig {
+        element_type: UIElementType::NormalText(|info| format!("Lives: {}", info.lives)),
+        x_pos: WINDOW_RIGHT_X - 115.0,
+        y_pos: WINDOW_TOP_Y - 1030.0,
+        font_size: 24.0,
+    },
     // Penetrate
     UIElementConfig {
         element_type: UIElementType::NormalText(|info| format!("Penetrate: {}", if info.penetrate { "On" } else { "Off" })),
@@ -364,6 +425,20 @@ pub const PLAYER2_UI_ELEMENTS: &[UIElementConfig] = &[
         y_pos: WINDOW_TOP_Y - 520.0,
         font_size: 24.0,
     },
+    // Ricochet
+    UIElementConfig {
+        element_type: UIElementType::NormalText(|info| format!("Ricochet:{}", if info.ricochet { "On" } else { "Off" })),
+        x_pos: WINDOW_RIGHT_X - 115.0,
+        y_pos: WINDOW_TOP_Y - 570.0,
+        font_size: 24.0,
+    },
+    // Missiles
+    UIElementConfig {
+        element_type: UIElementType::NormalText(|info| format!("Missiles: {}", info.missiles)),
+        x_pos: WINDOW_RIGHT_X - 115.0,
+        y_pos: WINDOW_TOP_Y - 620.0,
+        font_size: 24.0,
+    },
     // Fire Shell
     UIElementConfig {
         element_type: UIElementType::NormalText(|info| format!("Fire Shell:{}", if info.fire_shell { "On" } else { "Off" })),
@@ -416,10 +491,14 @@ pub enum GameState {
     FadingOut,
     StageIntro,
     Playing,
+    StageResults,
+    VersusResults,
     Paused,
+    NameEntry,
     GameOver,
     About,
     Credits,
+    HighScores,
 }
 
 #[derive(Component)]
@@ -433,6 +512,10 @@ pub struct MenuOption {
 #[derive(Component)]
 pub struct MenuArrow;
 
+// 开始界面的对局选项文本（友军误伤开关）
+#[derive(Component)]
+pub struct MatchOptionsText;
+
 #[derive(Component)]
 pub struct PauseUI;
 
@@ -486,6 +569,63 @@ pub struct EnemyTank {
 #[derive(Component)]
 pub struct EnemyBornAnimation;
 
+/// 敌方坦克类型（关卡结算按类型统计击毁数），战役只出现普通坦克，生存模式的后续波次会出现快速和重甲坦克
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EnemyClass {
+    #[default]
+    Basic,
+    Fast,    // 移动速度更快
+    Armored, // 移动较慢，需要多次命中
+}
+
+impl EnemyClass {
+    pub const ALL: [Self; 3] = [Self::Basic, Self::Fast, Self::Armored];
+
+    /// 结算界面显示的名称
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Basic => "Basic tanks",
+            Self::Fast => "Fast tanks",
+            Self::Armored => "Armored tanks",
+        }
+    }
+
+    /// 移动速度
+    pub const fn speed(self) -> f32 {
+        match self {
+            Self::Basic => TANK_SPEED,
+            Self::Fast => TANK_SPEED * 1.5,
+            Self::Armored => TANK_SPEED * 0.75,
+        }
+    }
+
+    /// 生命值
+    pub const fn health(self) -> usize {
+        match self {
+            Self::Basic | Self::Fast => 1,
+            Self::Armored => 3,
+        }
+    }
+
+    /// 击毁得分
+    pub const fn score(self) -> usize {
+        match self {
+            Self::Basic => 100,
+            Self::Fast => 200,
+            Self::Armored => 300,
+        }
+    }
+
+    /// 精灵图的染色（用于区分类型）
+    pub const fn tint(self) -> Color {
+        match self {
+            Self::Basic => Color::WHITE,
+            Self::Fast => Color::srgb(1.0, 0.6, 0.6),
+            Self::Armored => Color::srgb(0.7, 1.0, 0.7),
+        }
+    }
+}
+
 /// 坦克类型枚举
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TankType {
@@ -519,6 +659,14 @@ pub struct IsDashing;
 #[derive(Component)]
 pub struct BubbleEffect;
 
+// 玩家复活后的无敌状态，计时结束后移除
+#[derive(Component, Deref, DerefMut)]
+pub struct Invulnerable(pub Timer);
+
+// 无敌护罩特效标记（坦克的子实体）
+#[derive(Component)]
+pub struct InvulnerableEffect;
+
 #[derive(Component)]
 pub struct Explosion;
 
@@ -573,12 +721,38 @@ pub const BRICK_HEIGHT: f32 = 50.0;
 #[derive(Component)]
 pub struct Steel;
 
+/// 司令官周围的堡垒墙（砖块或加固后的钢铁）
+#[derive(Component)]
+pub struct CommanderWall;
+
 pub const STEEL_WIDTH: f32 = 50.0;
 pub const STEEL_HEIGHT: f32 = 50.0;
 
 #[derive(Component)]
 pub struct Commander;
 
+/// 对战模式中司令官属于哪名玩家（合作模式的司令官没有这个组件）
+#[derive(Component, Clone, Copy)]
+pub struct CommanderOwner(pub TankType);
+
+/// 生命值组件（所有坦克和司令官）
+/// 玩家坦克和司令官的生命值与 `PlayerStats.life_red_bar` / `CommanderLife` 保持同步，供 HUD 和道具使用
+#[derive(Component, Clone, Copy)]
+pub struct Health {
+    pub current: usize,
+    pub max: usize,
+}
+
+impl Health {
+    pub const fn new(max: usize) -> Self {
+        Self { current: max, max }
+    }
+}
+
+/// 护甲组件：防护百分比，每 40% 抵消 1 点撞击伤害
+#[derive(Component, Clone, Copy, Default)]
+pub struct Armor(pub usize);
+
 #[derive(Component, Clone, Copy, PartialEq, Eq)]
 pub enum PowerUp {
     SpeedUp,
@@ -591,6 +765,11 @@ pub enum PowerUp {
     Hamburger,
     AirCushion,
     Shell,
+    Clock,
+    Shovel,
+    Grenade,
+    Ricochet,
+    Missile,
 }
 
 impl PowerUp {
@@ -606,10 +785,90 @@ impl PowerUp {
             Self::Hamburger => "power_up/hamburger.png",
             Self::AirCushion => "power_up/air_cushion.png",
             Self::Shell => "power_up/shell.png",
+            Self::Clock => "power_up/clock.png",
+            Self::Shovel => "power_up/shovel.png",
+            Self::Grenade => "power_up/grenade.png",
+            Self::Ricochet => "power_up/ricochet.png",
+            Self::Missile => "power_up/missile.png",
         }
     }
 }
 
+/// 道具存在时间（秒），超时未拾取则消失
+pub const POWERUP_LIFETIME: f32 = 20.0;
+/// 道具消失前开始闪烁的时间（秒）
+pub const POWERUP_BLINK_TIME: f32 = 5.0;
+/// 道具生成间隔（秒）
+pub const POWERUP_SPAWN_INTERVAL: f32 = 15.0;
+/// 场上同时存在的道具上限
+pub const POWERUP_MAX_ACTIVE: usize = 2;
+/// 时钟道具冻结敌人的时间（秒）
+pub const ENEMY_FREEZE_DURATION: f32 = 6.0;
+/// 铁锹道具加固司令官堡垒的时间（秒）
+pub const FORTIFICATION_DURATION: f32 = 15.0;
+
+/// 各关卡道具权重表（索引为关卡序号减1，超出范围的关卡使用最后一项）
+pub const STAGE_POWERUP_WEIGHTS: &[&[(PowerUp, u32)]] = &[
+    // 第1关：以气垫为主，帮助玩家熟悉海面
+    &[
+        (PowerUp::AirCushion, 6),
+        (PowerUp::SpeedUp, 2),
+        (PowerUp::Repair, 1),
+        (PowerUp::Hamburger, 1),
+    ],
+    // 第2关
+    &[
+        (PowerUp::SpeedUp, 3),
+        (PowerUp::FireSpeed, 3),
+        (PowerUp::Protection, 2),
+        (PowerUp::Shell, 2),
+        (PowerUp::Clock, 1),
+        (PowerUp::Repair, 1),
+        (PowerUp::Hamburger, 1),
+    ],
+    // 第3关
+    &[
+        (PowerUp::FireShell, 3),
+        (PowerUp::TrackChain, 2),
+        (PowerUp::Penetrate, 2),
+        (PowerUp::Ricochet, 2),
+        (PowerUp::Protection, 2),
+        (PowerUp::Shell, 2),
+        (PowerUp::Clock, 1),
+        (PowerUp::Shovel, 1),
+        (PowerUp::Grenade, 1),
+        (PowerUp::Missile, 2),
+        (PowerUp::Repair, 1),
+        (PowerUp::Hamburger, 1),
+    ],
+    // 第4关及以后：全部道具
+    &[
+        (PowerUp::SpeedUp, 2),
+        (PowerUp::Protection, 2),
+        (PowerUp::FireSpeed, 2),
+        (PowerUp::FireShell, 2),
+        (PowerUp::TrackChain, 2),
+        (PowerUp::Penetrate, 2),
+        (PowerUp::Repair, 1),
+        (PowerUp::Hamburger, 1),
+        (PowerUp::AirCushion, 2),
+        (PowerUp::Shell, 2),
+        (PowerUp::Clock, 2),
+        (PowerUp::Shovel, 2),
+        (PowerUp::Grenade, 1),
+        (PowerUp::Ricochet, 2),
+        (PowerUp::Missile, 2),
+    ],
+];
+
+/// 道具剩余存在时间
+#[derive(Component, Deref, DerefMut)]
+pub struct PowerUpLifetime(pub Timer);
+
+/// 被时钟道具冻结的敌方坦克（剩余冻结时间）
+#[derive(Component, Deref, DerefMut)]
+pub struct Frozen(pub Timer);
+
 #[derive(Component)]
 pub struct HealthBar;
 
@@ -664,9 +923,6 @@ pub struct LaserChargeSound;
 #[derive(Component, Deref, DerefMut)]
 pub struct PlayerInfoBlinkTimer(pub Timer);
 
-#[derive(Resource, Deref, DerefMut)]
-pub struct PlayerRespawnTimer(pub Timer);
-
 /// 坦克射击配置
 #[derive(Component)]
 pub struct TankFireConfig {
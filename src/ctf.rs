@@ -0,0 +1,233 @@
+//! 夺旗模块
+//!
+//! 夺旗规则下敌方基地（关卡文件的 @flag 格子）插着一面旗帜。玩家坦克开到旗帜上就会扛起旗帜，
+//! 带回司令官身边算夺回一次，旗帜随即回到敌方基地；扛旗的坦克被摧毁时旗帜掉在原地，
+//! 任何玩家都可以捡起；扛旗者开始回城时旗帜同样掉在原地。有人扛旗时敌方坦克会转向追击扛旗者。
+//! 夺回足够次数即过关
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::constants::{
+    Commander, EnemyTank, Frozen, PlayerTank, PlayingEntity, SOUND_POWERUP, TankType,
+};
+use crate::resources::{
+    CaptureFlagState, GameMode, GameRng, MatchOptions, MatchRule, PlayerInfo, PlayerStatChanged,
+    StatType,
+};
+
+/// 每关需要夺回的旗帜数
+pub const FLAG_CAPTURES_TO_WIN: usize = 3;
+/// 每夺回一次旗帜的奖励分数
+const FLAG_CAPTURE_SCORE: usize = 1000;
+/// 捡起旗帜的距离（像素）
+const FLAG_PICKUP_RANGE: f32 = 60.0;
+/// 扛旗的坦克与司令官的距离小于这个值时夺回旗帜（像素）
+const FLAG_CAPTURE_RANGE: f32 = 150.0;
+/// 扛旗时旗帜相对坦克中心的偏移
+const FLAG_CARRY_OFFSET: Vec2 = Vec2::new(0.0, 50.0);
+/// 敌方坦克在调整方向时转向扛旗者的概率
+const HUNT_CHANCE: f32 = 0.7;
+/// 关卡文件没有指定旗帜位置时使用的格子（敌方出生区下方的中间）
+const DEFAULT_FLAG_CELL: (usize, usize) = (1, crate::map::MAP_COLS / 2);
+
+/// 旗帜的状态
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FlagState {
+    Home,            // 插在敌方基地
+    Carried(Entity), // 被玩家坦克扛着
+    Dropped,         // 扛旗者被摧毁后掉在地上
+}
+
+/// 旗帜
+#[derive(Component)]
+pub struct Flag {
+    pub state: FlagState,
+}
+
+/// 顶部显示的夺旗进度和旗帜状态
+#[derive(Component)]
+pub struct FlagStatusText;
+
+/// 在敌方基地生成旗帜（旗杆和旗面），重置本关的夺旗进度
+pub fn spawn_flag(commands: &mut Commands, capture_flag_state: &mut CaptureFlagState, stage_level: usize) {
+    let (row, col) = crate::levels::get_level_meta(stage_level).flag.unwrap_or(DEFAULT_FLAG_CELL);
+    let home = crate::map::grid_to_world(row, col);
+    capture_flag_state.home = home;
+    capture_flag_state.captures = 0;
+    capture_flag_state.hunt_timer.reset();
+
+    commands
+        .spawn((
+            PlayingEntity,
+            Flag { state: FlagState::Home },
+            Transform::from_translation(home.extend(3.0)), // 在坦克上方
+            Visibility::default(),
+        ))
+        .with_children(|parent| {
+            // 旗杆
+            parent.spawn((
+                Sprite {
+                    color: Color::srgb(0.85, 0.85, 0.85),
+                    custom_size: Some(Vec2::new(6.0, 70.0)),
+                    ..default()
+                },
+                Transform::from_xyz(-18.0, 0.0, 0.0),
+            ));
+            // 旗面
+            parent.spawn((
+                Sprite {
+                    color: Color::srgb(1.0, 0.1, 0.1),
+                    custom_size: Some(Vec2::new(40.0, 28.0)),
+                    ..default()
+                },
+                Transform::from_xyz(5.0, 20.0, 0.1),
+            ));
+        });
+}
+
+const fn player_label(player_type: TankType) -> &'static str {
+    match player_type {
+        TankType::Player1 => "Player 1",
+        TankType::Player2 => "Player 2",
+        TankType::Enemy => "Enemy",
+    }
+}
+
+/// 旗帜系统：捡起旗帜、跟随扛旗者、扛旗者被摧毁或开始回城时掉落、带回司令官身边时夺回
+pub fn update_flag(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    mut capture_flag_state: ResMut<CaptureFlagState>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+    mut flags: Query<(&mut Flag, &mut Transform)>,
+    player_tanks: Query<(Entity, &Transform, &PlayerTank, Has<crate::IsRecalling>), Without<Flag>>,
+    commanders: Query<&Transform, (With<Commander>, Without<Flag>, Without<PlayerTank>)>,
+) {
+    if match_options.active_rule(*game_mode) != MatchRule::CaptureFlag {
+        return;
+    }
+
+    for (mut flag, mut transform) in &mut flags {
+        match flag.state {
+            FlagState::Home | FlagState::Dropped => {
+                // 最先开到旗帜上的玩家坦克扛起旗帜（正在回城的坦克不会捡起）
+                let flag_position = transform.translation.truncate();
+                let carrier = player_tanks.iter().find(|(_, tank_transform, _, is_recalling)| {
+                    !is_recalling && tank_transform.translation.truncate().distance(flag_position) <= FLAG_PICKUP_RANGE
+                });
+                if let Some((carrier, _, _, _)) = carrier {
+                    flag.state = FlagState::Carried(carrier);
+                    let powerup_sound: Handle<AudioSource> = asset_server.load(SOUND_POWERUP);
+                    commands.spawn(AudioPlayer::new(powerup_sound));
+                }
+            }
+            FlagState::Carried(carrier) => {
+                // 扛旗者被摧毁：旗帜掉在它最后的位置
+                let Ok((_, tank_transform, player_tank, is_recalling)) = player_tanks.get(carrier) else {
+                    flag.state = FlagState::Dropped;
+                    transform.translation -= FLAG_CARRY_OFFSET.extend(0.0);
+                    continue;
+                };
+
+                // 扛旗者开始回城：旗帜掉在原地，不能靠回城直接传送回司令官身边
+                if is_recalling {
+                    flag.state = FlagState::Dropped;
+                    transform.translation = tank_transform.translation.truncate().extend(3.0);
+                    continue;
+                }
+
+                let tank_position = tank_transform.translation.truncate();
+                transform.translation = (tank_position + FLAG_CARRY_OFFSET).extend(3.0);
+
+                // 带回司令官身边：夺回一次，旗帜回到敌方基地
+                let is_home = commanders
+                    .iter()
+                    .any(|commander_transform| commander_transform.translation.truncate().distance(tank_position) <= FLAG_CAPTURE_RANGE);
+                if !is_home {
+                    continue;
+                }
+                capture_flag_state.captures += 1;
+                flag.state = FlagState::Home;
+                transform.translation = capture_flag_state.home.extend(3.0);
+
+                if let Some(player_stats) = player_info.players.get_mut(&player_tank.tank_type) {
+                    player_stats.score += FLAG_CAPTURE_SCORE;
+                    stat_changed_events.write(PlayerStatChanged {
+                        player_type: player_tank.tank_type,
+                        stat_type: StatType::Score,
+                    });
+                }
+
+                let powerup_sound: Handle<AudioSource> = asset_server.load(SOUND_POWERUP);
+                commands.spawn(AudioPlayer::new(powerup_sound));
+            }
+        }
+    }
+}
+
+/// 追击系统：有人扛旗时，敌方坦克每隔一段时间大概率转向扛旗者（沿距离更远的轴移动）
+pub fn hunt_flag_carrier(
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    mut capture_flag_state: ResMut<CaptureFlagState>,
+    mut rng: ResMut<GameRng>,
+    flags: Query<&Flag>,
+    player_tanks: Query<&Transform, With<PlayerTank>>,
+    mut enemies: Query<(&Transform, &mut EnemyTank), (Without<PlayerTank>, Without<Frozen>)>,
+) {
+    if match_options.active_rule(*game_mode) != MatchRule::CaptureFlag {
+        return;
+    }
+
+    capture_flag_state.hunt_timer.tick(time.delta());
+    if !capture_flag_state.hunt_timer.just_finished() {
+        return;
+    }
+
+    let carrier_position = flags.iter().find_map(|flag| match flag.state {
+        FlagState::Carried(carrier) => player_tanks.get(carrier).ok().map(|transform| transform.translation.truncate()),
+        FlagState::Home | FlagState::Dropped => None,
+    });
+    let Some(carrier_position) = carrier_position else {
+        return;
+    };
+
+    for (transform, mut enemy_tank) in &mut enemies {
+        // 保留一定的随机性，避免被地形卡住时一直朝同一个方向撞
+        if rng.random::<f32>() >= HUNT_CHANCE {
+            continue;
+        }
+        let offset = carrier_position - transform.translation.truncate();
+        enemy_tank.direction = if offset.x.abs() > offset.y.abs() {
+            Vec2::new(offset.x.signum(), 0.0)
+        } else {
+            Vec2::new(0.0, offset.y.signum())
+        };
+    }
+}
+
+/// 更新顶部的夺旗进度和旗帜状态
+pub fn update_flag_status_text(
+    capture_flag_state: Res<CaptureFlagState>,
+    flags: Query<&Flag>,
+    player_tanks: Query<&PlayerTank>,
+    mut status_texts: Query<(&mut Text2d, &mut TextColor), With<FlagStatusText>>,
+) {
+    let (status, color) = match flags.iter().next().map(|flag| flag.state) {
+        Some(FlagState::Carried(carrier)) => {
+            let carrier_label = player_tanks.get(carrier).map_or("Player", |player_tank| player_label(player_tank.tank_type));
+            (format!("{carrier_label} has the flag!"), Color::srgb(0.0, 1.0, 0.0))
+        }
+        Some(FlagState::Dropped) => ("Flag dropped".to_string(), Color::srgb(1.0, 0.5, 0.0)),
+        Some(FlagState::Home) | None => ("Flag at enemy base".to_string(), Color::srgb(1.0, 1.0, 1.0)),
+    };
+    for (mut text, mut text_color) in &mut status_texts {
+        text.0 = format!("Flags {}/{}   {}", capture_flag_state.captures, FLAG_CAPTURES_TO_WIN, status);
+        text_color.0 = color;
+    }
+}
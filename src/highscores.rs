@@ -0,0 +1,538 @@
+//! 高分榜模块
+//!
+//! 高分榜按游戏模式（单人 / 双人）和对局规则（战役 / 生存）分别保存前 10 名，写入用户数据目录下的文本文件；
+//! 游戏结束后分数上榜的玩家依次输入 3 个字符的名字（街机风格），开始界面可以查看高分榜
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::constants::{EnemyTank, GameState, TankType, WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::resources::{
+    CampaignRun, GameMode, MatchOptions, MatchRule, PlayerInfo, StageLevel, SurvivalState,
+};
+
+/// 高分榜文件名（在用户数据目录下）
+const HIGH_SCORE_FILE: &str = "highscores.txt";
+/// 每种模式保留的记录数
+const HIGH_SCORE_LIMIT: usize = 10;
+/// 名字长度
+const NAME_LENGTH: usize = 3;
+/// 名字可用的字符
+const NAME_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// 一条高分记录
+#[derive(Clone)]
+pub struct HighScoreEntry {
+    pub name: String,
+    pub score: usize,
+    pub stage: usize, // 到达的关卡（生存模式为到达的波次）
+    pub date: String, // YYYY-MM-DD
+    pub seed: u32,    // 本局的种子
+}
+
+/// 高分榜
+#[derive(Resource, Default)]
+pub struct HighScoreTable {
+    pub one_player: Vec<HighScoreEntry>,
+    pub two_players: Vec<HighScoreEntry>,
+    pub survival_one_player: Vec<HighScoreEntry>,
+    pub survival_two_players: Vec<HighScoreEntry>,
+}
+
+impl HighScoreTable {
+    pub fn entries(&self, mode: GameMode, rule: MatchRule) -> &[HighScoreEntry] {
+        match (mode, rule) {
+            (GameMode::OnePlayer, MatchRule::Campaign) => &self.one_player,
+            (GameMode::TwoPlayers, MatchRule::Campaign) => &self.two_players,
+            (GameMode::OnePlayer, MatchRule::Survival) => &self.survival_one_player,
+            (GameMode::TwoPlayers, MatchRule::Survival) => &self.survival_two_players,
+            (GameMode::Versus, _) | (_, MatchRule::TimeAttack | MatchRule::CaptureFlag | MatchRule::KingOfTheHill) => &[],
+        }
+    }
+
+    /// 对战模式、限时挑战、夺旗和占山为王不记录高分
+    const fn entries_mut(&mut self, mode: GameMode, rule: MatchRule) -> Option<&mut Vec<HighScoreEntry>> {
+        match (mode, rule) {
+            (GameMode::OnePlayer, MatchRule::Campaign) => Some(&mut self.one_player),
+            (GameMode::TwoPlayers, MatchRule::Campaign) => Some(&mut self.two_players),
+            (GameMode::OnePlayer, MatchRule::Survival) => Some(&mut self.survival_one_player),
+            (GameMode::TwoPlayers, MatchRule::Survival) => Some(&mut self.survival_two_players),
+            (GameMode::Versus, _) | (_, MatchRule::TimeAttack | MatchRule::CaptureFlag | MatchRule::KingOfTheHill) => None,
+        }
+    }
+
+    /// 分数能否上榜
+    pub fn qualifies(&self, mode: GameMode, rule: MatchRule, score: usize) -> bool {
+        if mode == GameMode::Versus || matches!(rule, MatchRule::TimeAttack | MatchRule::CaptureFlag | MatchRule::KingOfTheHill) {
+            return false;
+        }
+        let entries = self.entries(mode, rule);
+        score > 0 && (entries.len() < HIGH_SCORE_LIMIT || entries.iter().any(|entry| score > entry.score))
+    }
+
+    /// 插入记录，按分数从高到低排序（同分时先上榜的在前），只保留前 `HIGH_SCORE_LIMIT` 名
+    pub fn insert(&mut self, mode: GameMode, rule: MatchRule, entry: HighScoreEntry) {
+        let Some(entries) = self.entries_mut(mode, rule) else {
+            return;
+        };
+        let index = entries.iter().position(|existing| entry.score > existing.score).unwrap_or(entries.len());
+        entries.insert(index, entry);
+        entries.truncate(HIGH_SCORE_LIMIT);
+    }
+}
+
+/// 高分榜中保存的模式和规则组合
+const BOARDS: [(GameMode, MatchRule); 4] = [
+    (GameMode::OnePlayer, MatchRule::Campaign),
+    (GameMode::TwoPlayers, MatchRule::Campaign),
+    (GameMode::OnePlayer, MatchRule::Survival),
+    (GameMode::TwoPlayers, MatchRule::Survival),
+];
+
+/// 高分榜文件中的模式标记
+const fn mode_tag(mode: GameMode, rule: MatchRule) -> &'static str {
+    match (mode, rule) {
+        (GameMode::OnePlayer, MatchRule::Campaign) => "1P",
+        (GameMode::TwoPlayers, MatchRule::Campaign) => "2P",
+        (GameMode::OnePlayer, MatchRule::Survival) => "1P-SURVIVAL",
+        (GameMode::TwoPlayers, MatchRule::Survival) => "2P-SURVIVAL",
+        (GameMode::Versus, _) => "VS",
+        (_, MatchRule::TimeAttack) => "TA",
+        (_, MatchRule::CaptureFlag) => "CTF",
+        (_, MatchRule::KingOfTheHill) => "KOTH",
+    }
+}
+
+/// 用户数据目录下 tank-battle 中的文件路径（高分榜、限时挑战的最好成绩）
+pub fn user_data_file(file_name: &str) -> Option<PathBuf> {
+    let data_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+    }?;
+    Some(data_dir.join("tank-battle").join(file_name))
+}
+
+/// 解析一行记录：模式、名字、分数、关卡、日期、种子，以制表符分隔
+fn parse_entry(line: &str) -> Result<(GameMode, MatchRule, HighScoreEntry), String> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let [mode, name, score, stage, date, seed] = fields[..] else {
+        return Err(format!("expected 6 fields, found {}", fields.len()));
+    };
+    let Some(&(mode, rule)) = BOARDS.iter().find(|&&(board_mode, board_rule)| mode_tag(board_mode, board_rule) == mode) else {
+        return Err(format!("unknown mode: {mode}"));
+    };
+    let entry = HighScoreEntry {
+        name: name.to_string(),
+        score: score.parse().map_err(|e| format!("invalid score: {e}"))?,
+        stage: stage.parse().map_err(|e| format!("invalid stage: {e}"))?,
+        date: date.to_string(),
+        seed: seed.parse().map_err(|e| format!("invalid seed: {e}"))?,
+    };
+    Ok((mode, rule, entry))
+}
+
+/// 读取高分榜，文件不存在时返回空表
+pub fn load_high_scores() -> HighScoreTable {
+    let mut table = HighScoreTable::default();
+    let Some(path) = user_data_file(HIGH_SCORE_FILE) else {
+        return table;
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return table;
+    };
+
+    for line in content.lines() {
+        // 跳过空行和注释
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_entry(line) {
+            Ok((mode, rule, entry)) => table.insert(mode, rule, entry),
+            Err(e) => eprintln!("Warning: Skipping invalid high score line in {}: {}", path.display(), e),
+        }
+    }
+    table
+}
+
+/// 写入高分榜
+fn save_high_scores(table: &HighScoreTable) {
+    let Some(path) = user_data_file(HIGH_SCORE_FILE) else {
+        eprintln!("Warning: No user data directory, high scores are not saved");
+        return;
+    };
+
+    let mut content = String::from("# mode\tname\tscore\tstage\tdate\tseed\n");
+    for (mode, rule) in BOARDS {
+        for entry in table.entries(mode, rule) {
+            let _ = writeln!(
+                content,
+                "{}\t{}\t{}\t{}\t{}\t{}",
+                mode_tag(mode, rule),
+                entry.name,
+                entry.score,
+                entry.stage,
+                entry.date,
+                entry.seed
+            );
+        }
+    }
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, content));
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to save high scores to {}: {}", path.display(), e);
+    }
+}
+
+/// 今天的日期（UTC），格式 YYYY-MM-DD
+fn today() -> String {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    // 把 1970-01-01 以来的天数换算成公历日期
+    let days = i64::try_from(secs / 86_400).unwrap_or(0) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// 名字输入状态
+#[derive(Resource, Default)]
+pub struct NameEntry {
+    pub pending: Vec<(TankType, usize)>, // 等待输入名字的玩家和分数
+    pub letters: [usize; NAME_LENGTH],   // 每一位字符在 NAME_CHARS 中的下标
+    pub cursor: usize,                   // 当前编辑的位置
+}
+
+impl NameEntry {
+    fn name(&self) -> String {
+        self.letters.iter().map(|&index| NAME_CHARS[index] as char).collect()
+    }
+}
+
+/// 名字输入界面的元素
+#[derive(Component)]
+pub struct NameEntryUI;
+
+/// 名字输入界面的提示文字（显示当前玩家和分数）
+#[derive(Component)]
+pub struct NameEntryPrompt;
+
+/// 名字中的一位字符
+#[derive(Component)]
+pub struct NameEntryLetter {
+    pub index: usize,
+}
+
+/// 高分榜界面的元素
+#[derive(Component)]
+pub struct HighScoresUI;
+
+/// 进入名字输入界面：找出分数上榜的玩家，没有时直接进入结束界面
+pub fn spawn_name_entry(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    player_info: Res<PlayerInfo>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    table: Res<HighScoreTable>,
+    mut name_entry: ResMut<NameEntry>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut enemy_velocity_query: Query<&mut Velocity, With<EnemyTank>>,
+) {
+    name_entry.pending = [TankType::Player1, TankType::Player2]
+        .into_iter()
+        .filter_map(|player_type| player_info.players.get(&player_type).map(|stats| (player_type, stats.score)))
+        .filter(|&(_, score)| table.qualifies(*game_mode, match_options.active_rule(*game_mode), score))
+        .collect();
+    name_entry.letters = [0; NAME_LENGTH];
+    name_entry.cursor = 0;
+
+    if name_entry.pending.is_empty() {
+        next_state.set(GameState::GameOver);
+        return;
+    }
+
+    // 停止敌方坦克的移动
+    for mut velocity in &mut enemy_velocity_query {
+        velocity.linvel = Vec2::ZERO;
+    }
+
+    let font: Handle<Font> = asset_server.load(crate::FONT_EN);
+
+    // 半透明背景
+    commands.spawn((
+        NameEntryUI,
+        Sprite {
+            color: Color::srgba(0.0, 0.0, 0.0, 0.8),
+            custom_size: Some(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 9.0),
+    ));
+
+    commands.spawn((
+        NameEntryUI,
+        Text2d("NEW HIGH SCORE".to_string()),
+        TextFont {
+            font_size: 100.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.8, 0.0)),
+        Transform::from_xyz(0.0, 200.0, 10.0),
+    ));
+
+    commands.spawn((
+        NameEntryUI,
+        NameEntryPrompt,
+        Text2d::default(),
+        TextFont {
+            font_size: 40.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        Transform::from_xyz(0.0, 80.0, 10.0),
+    ));
+
+    for index in 0..NAME_LENGTH {
+        commands.spawn((
+            NameEntryUI,
+            NameEntryLetter { index },
+            Text2d::default(),
+            TextFont {
+                font_size: 120.0,
+                font: font.clone(),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            Transform::from_xyz((index as f32 - 1.0) * 100.0, -60.0, 10.0),
+        ));
+    }
+
+    // 操作说明
+    commands.spawn((
+        NameEntryUI,
+        Text2d("W/S to change letter | A/D to move | SPACE to confirm".to_string()),
+        TextFont {
+            font_size: 30.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        Transform::from_xyz(0.0, -200.0, 10.0),
+    ));
+}
+
+/// 名字输入：上下切换字符，左右移动光标，空格确认；所有上榜玩家输入完后进入结束界面
+pub fn handle_name_entry_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut name_entry: ResMut<NameEntry>,
+    mut table: ResMut<HighScoreTable>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    stage_level: Res<StageLevel>,
+    survival_state: Res<SurvivalState>,
+    campaign_run: Res<CampaignRun>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let cursor = name_entry.cursor;
+    if keyboard_input.any_just_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+        name_entry.letters[cursor] = (name_entry.letters[cursor] + NAME_CHARS.len() - 1) % NAME_CHARS.len();
+    }
+    if keyboard_input.any_just_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+        name_entry.letters[cursor] = (name_entry.letters[cursor] + 1) % NAME_CHARS.len();
+    }
+    if keyboard_input.any_just_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) {
+        name_entry.cursor = cursor.saturating_sub(1);
+    }
+    if keyboard_input.any_just_pressed([KeyCode::KeyD, KeyCode::ArrowRight]) {
+        name_entry.cursor = (cursor + 1).min(NAME_LENGTH - 1);
+    }
+
+    if !keyboard_input.just_pressed(KeyCode::Space) || name_entry.pending.is_empty() {
+        return;
+    }
+
+    // 保存当前玩家的记录（生存模式记录到达的波次）
+    let (_, score) = name_entry.pending.remove(0);
+    let rule = match_options.active_rule(*game_mode);
+    let stage = match rule {
+        MatchRule::Campaign | MatchRule::TimeAttack | MatchRule::CaptureFlag | MatchRule::KingOfTheHill => stage_level.0,
+        MatchRule::Survival => survival_state.wave,
+    };
+    table.insert(
+        *game_mode,
+        rule,
+        HighScoreEntry {
+            name: name_entry.name(),
+            score,
+            stage,
+            date: today(),
+            seed: campaign_run.seed,
+        },
+    );
+    save_high_scores(&table);
+
+    // 下一名上榜玩家从头输入，全部输入完后进入结束界面
+    name_entry.letters = [0; NAME_LENGTH];
+    name_entry.cursor = 0;
+    if name_entry.pending.is_empty() {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// 更新名字输入界面的文字
+pub fn update_name_entry_ui(
+    name_entry: Res<NameEntry>,
+    player_info: Res<PlayerInfo>,
+    mut prompts: Query<&mut Text2d, (With<NameEntryPrompt>, Without<NameEntryLetter>)>,
+    mut letters: Query<(&NameEntryLetter, &mut Text2d, &mut TextColor)>,
+) {
+    let Some(&(player_type, score)) = name_entry.pending.first() else {
+        return;
+    };
+    let player_name = player_info.players.get(&player_type).map(|stats| stats.name.as_str()).unwrap_or_default();
+    for mut text in &mut prompts {
+        text.0 = format!("{player_name}  {score}");
+    }
+
+    for (letter, mut text, mut text_color) in &mut letters {
+        text.0 = (NAME_CHARS[name_entry.letters[letter.index]] as char).to_string();
+        // 正在编辑的字符使用黄色
+        text_color.0 = if letter.index == name_entry.cursor {
+            Color::srgb(1.0, 1.0, 0.0)
+        } else {
+            Color::srgb(1.0, 1.0, 1.0)
+        };
+    }
+}
+
+/// 清理名字输入界面
+pub fn despawn_name_entry(mut commands: Commands, query: Query<Entity, With<NameEntryUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+/// 生成高分榜界面：左右两列分别是单人和双人模式，上面是战役、下面是生存模式
+pub fn spawn_high_scores_screen(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    table: Res<HighScoreTable>,
+) {
+    // 加载自定义字体
+    let custom_font: Handle<Font> = asset_server.load(crate::FONT_EN);
+
+    // 添加白色背景覆盖
+    commands.spawn((
+        HighScoresUI,
+        Sprite {
+            color: Color::srgb(1.0, 1.0, 1.0),
+            custom_size: Some(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 0.0),
+    ));
+
+    // 添加标题
+    commands.spawn((
+        HighScoresUI,
+        Text2d("High Scores".to_string()),
+        TextFont {
+            font_size: 80.0,
+            font: custom_font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(0.0, 0.0, 0.0)),
+        Transform::from_xyz(0.0, 560.0, 1.0),
+    ));
+
+    for (mode, rule, title, x, y) in [
+        (GameMode::OnePlayer, MatchRule::Campaign, "1 Player", -480.0, 270.0),
+        (GameMode::TwoPlayers, MatchRule::Campaign, "2 Player", 480.0, 270.0),
+        (GameMode::OnePlayer, MatchRule::Survival, "1 Player Survival", -480.0, -190.0),
+        (GameMode::TwoPlayers, MatchRule::Survival, "2 Player Survival", 480.0, -190.0),
+    ] {
+        let progress_label = match rule {
+            MatchRule::Campaign | MatchRule::TimeAttack | MatchRule::CaptureFlag | MatchRule::KingOfTheHill => "Stage",
+            MatchRule::Survival => "Wave ",
+        };
+        let rows: Vec<String> = table
+            .entries(mode, rule)
+            .iter()
+            .enumerate()
+            .map(|(rank, entry)| {
+                format!(
+                    "{:>2}. {}  {:>7}  {} {:<2}  {}  #{}",
+                    rank + 1,
+                    entry.name,
+                    entry.score,
+                    progress_label,
+                    entry.stage,
+                    entry.date,
+                    entry.seed
+                )
+            })
+            .collect();
+        let body = if rows.is_empty() { "No records yet".to_string() } else { rows.join("\n") };
+
+        commands.spawn((
+            HighScoresUI,
+            Text2d(format!("{title}\n\n{body}")),
+            TextFont {
+                font_size: 24.0,
+                font: custom_font.clone(),
+                ..default()
+            },
+            TextColor(Color::srgb(0.0, 0.0, 0.0)),
+            TextLayout::new_with_justify(Justify::Left),
+            Transform::from_xyz(x, y, 1.0),
+        ));
+    }
+
+    // 返回提示
+    commands.spawn((
+        HighScoresUI,
+        Text2d("Press SPACE to return".to_string()),
+        TextFont {
+            font_size: 30.0,
+            font: custom_font,
+            ..default()
+        },
+        TextColor(Color::srgb(0.0, 0.0, 0.0)),
+        Transform::from_xyz(0.0, -550.0, 1.0),
+    ));
+}
+
+pub fn despawn_high_scores_screen(mut commands: Commands, query: Query<Entity, With<HighScoresUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).try_despawn();
+    }
+}
+
+pub fn handle_high_scores_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Space 键返回开始界面
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        next_state.set(GameState::StartScreen);
+    }
+}
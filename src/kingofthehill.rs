@@ -0,0 +1,183 @@
+//! 占山为王模块
+//!
+//! 占山为王规则下地图上有一个或多个占领区（关卡文件的 @zone 行），以染色的格子显示。
+//! 玩家坦克停在占领区内时积累控制分，占领区上方的进度条每读满一次得 1 分；
+//! 敌方坦克进入占领区会争夺它，进度条清零。本关控制分达到目标即过关
+
+use bevy::prelude::*;
+
+use crate::constants::{EnemyTank, PlayerTank, PlayingEntity, TankType};
+use crate::resources::{
+    GameMode, KingOfTheHillState, MatchOptions, MatchRule, PlayerInfo, PlayerStatChanged, StatType,
+};
+
+/// 每关需要的控制分
+pub const HILL_TARGET_SCORE: usize = 20;
+/// 进度条读满一次（得 1 控制分）的时间（秒）
+const ZONE_POINT_TIME: f32 = 2.0;
+/// 每得 1 控制分，占领区内的每名玩家获得的分数
+const ZONE_POINT_SCORE: usize = 100;
+/// 关卡文件没有指定占领区时使用的占领区（地图中间的 2x2 格子）
+const DEFAULT_ZONE: crate::levels::LevelZone = crate::levels::LevelZone { row: 5, col: 7, rows: 2, cols: 2 };
+
+/// 占领区
+#[derive(Component)]
+pub struct ControlZone {
+    pub size: Vec2,    // 占领区的尺寸（像素）
+    pub progress: f32, // 当前进度条的读条时间（秒）
+}
+
+/// 占领区的染色格子
+#[derive(Component)]
+pub struct ZoneTile;
+
+/// 占领区上方的进度条
+#[derive(Component)]
+pub struct ZoneProgressBar {
+    pub zone: Entity,
+}
+
+/// 顶部显示的控制分
+#[derive(Component)]
+pub struct HillScoreText;
+
+/// 占领区的颜色：无人时黄色，玩家占领时绿色，敌方争夺时红色
+const fn zone_color(held: bool, contested: bool) -> Color {
+    if contested {
+        Color::srgba(1.0, 0.2, 0.2, 0.35)
+    } else if held {
+        Color::srgba(0.2, 1.0, 0.2, 0.35)
+    } else {
+        Color::srgba(1.0, 0.85, 0.0, 0.25)
+    }
+}
+
+/// 按关卡文件生成占领区（染色格子和进度条）
+pub fn spawn_control_zones(commands: &mut Commands, stage_level: usize) {
+    let mut zones = crate::levels::get_level_meta(stage_level).zones;
+    if zones.is_empty() {
+        zones.push(DEFAULT_ZONE);
+    }
+
+    for zone in zones {
+        let top_left = crate::map::grid_to_world(zone.row, zone.col);
+        let bottom_right = crate::map::grid_to_world(zone.row + zone.rows - 1, zone.col + zone.cols - 1);
+        let center = (top_left + bottom_right) / 2.0;
+        let size = Vec2::new(zone.cols as f32, zone.rows as f32) * crate::map::GRID_SIZE;
+
+        let zone_entity = commands
+            .spawn((
+                PlayingEntity,
+                ControlZone { size, progress: 0.0 },
+                Transform::from_translation(center.extend(-0.6)), // 在地形下方
+                Visibility::default(),
+            ))
+            .with_children(|parent| {
+                for row in zone.row..zone.row + zone.rows {
+                    for col in zone.col..zone.col + zone.cols {
+                        let offset = crate::map::grid_to_world(row, col) - center;
+                        parent.spawn((
+                            ZoneTile,
+                            Sprite {
+                                color: zone_color(false, false),
+                                custom_size: Some(Vec2::splat(crate::map::GRID_SIZE - 4.0)), // 格子之间留出缝隙
+                                ..default()
+                            },
+                            Transform::from_translation(offset.extend(0.0)),
+                        ));
+                    }
+                }
+            })
+            .id();
+
+        // 进度条在占领区正上方，初始为空
+        commands.spawn((
+            PlayingEntity,
+            ZoneProgressBar { zone: zone_entity },
+            Sprite {
+                color: Color::srgb(0.0, 1.0, 0.0), // 绿色
+                custom_size: Some(Vec2::new(0.0, 8.0)),
+                ..default()
+            },
+            Transform::from_xyz(center.x, center.y + size.y / 2.0 + 10.0, 2.0),
+        ));
+    }
+}
+
+/// 进入关卡介绍时清零本关的控制分
+pub fn reset_hill_score(mut hill_state: ResMut<KingOfTheHillState>) {
+    hill_state.score = 0;
+}
+
+/// 占领区系统：只有玩家坦克在区内时读条得分，有敌方坦克在区内时进度条清零
+pub fn update_control_zones(
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    mut hill_state: ResMut<KingOfTheHillState>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+    mut zones: Query<(Entity, &mut ControlZone, &Transform, &Children)>,
+    player_tanks: Query<(&Transform, &PlayerTank)>,
+    enemies: Query<&Transform, With<EnemyTank>>,
+    mut tiles: Query<&mut Sprite, (With<ZoneTile>, Without<ZoneProgressBar>)>,
+    mut progress_bars: Query<(&ZoneProgressBar, &mut Sprite, &mut Transform), (Without<ZoneTile>, Without<ControlZone>)>,
+) {
+    if match_options.active_rule(*game_mode) != MatchRule::KingOfTheHill {
+        return;
+    }
+
+    for (zone_entity, mut zone, zone_transform, children) in &mut zones {
+        let zone_rect = Rect::from_center_size(zone_transform.translation.truncate(), zone.size);
+        let players_inside: Vec<TankType> = player_tanks
+            .iter()
+            .filter(|(transform, _)| zone_rect.contains(transform.translation.truncate()))
+            .map(|(_, player_tank)| player_tank.tank_type)
+            .collect();
+        let contested = enemies.iter().any(|transform| zone_rect.contains(transform.translation.truncate()));
+        let held = !players_inside.is_empty() && !contested;
+
+        if contested {
+            zone.progress = 0.0;
+        } else if held {
+            zone.progress += time.delta_secs();
+            if zone.progress >= ZONE_POINT_TIME {
+                zone.progress -= ZONE_POINT_TIME;
+                hill_state.score += 1;
+                for player_type in players_inside {
+                    if let Some(player_stats) = player_info.players.get_mut(&player_type) {
+                        player_stats.score += ZONE_POINT_SCORE;
+                        stat_changed_events.write(PlayerStatChanged {
+                            player_type,
+                            stat_type: StatType::Score,
+                        });
+                    }
+                }
+            }
+        }
+
+        // 染色格子
+        for child in children.iter() {
+            if let Ok(mut sprite) = tiles.get_mut(child) {
+                sprite.color = zone_color(held, contested);
+            }
+        }
+
+        // 进度条从左向右增长
+        for (progress_bar, mut sprite, mut transform) in &mut progress_bars {
+            if progress_bar.zone != zone_entity {
+                continue;
+            }
+            let bar_width = zone.size.x * zone.progress / ZONE_POINT_TIME;
+            sprite.custom_size = Some(Vec2::new(bar_width, 8.0));
+            transform.translation.x = zone_rect.min.x + bar_width / 2.0;
+        }
+    }
+}
+
+/// 更新顶部的控制分
+pub fn update_hill_score_text(hill_state: Res<KingOfTheHillState>, mut score_texts: Query<&mut Text2d, With<HillScoreText>>) {
+    for mut text in &mut score_texts {
+        text.0 = format!("Control {}/{}", hill_state.score, HILL_TARGET_SCORE);
+    }
+}
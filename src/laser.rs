@@ -1,12 +1,15 @@
 //! 激光系统模块
 //!
-//! 处理激光的生成、动画和蓝量消耗
+//! 处理激光的生成、动画、蓝量消耗和命中判定
 
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use std::collections::HashSet;
 
+use crate::combat::{Damage, DamageKind};
 use crate::constants::*;
 use crate::resources::*;
-use crate::bullet::BulletOwner;
+use crate::bullet::{Bullet, BulletDespawnMarker, BulletOwner};
 use crate::constants::{RecoilForce, LaserCharge, LaserChargeProgressBar, LaserChargeSound};
 
 /// 激光生成参数
@@ -16,6 +19,14 @@ pub struct LaserSpawnParams {
     pub owner_type: TankType,
 }
 
+/// 激光束组件：记录光束起点和方向，用于命中判定和被钢铁截断后的长度调整
+#[derive(Component)]
+pub struct LaserBeam {
+    pub start: Vec2,          // 光束起点（贴图底部）
+    pub direction: Vec2,      // 光束方向（单位向量）
+    pub hit: HashSet<Entity>, // 已经受到这道光束伤害的坦克，每道光束对同一目标只造成一次伤害
+}
+
 /// 生成激光实体（像手电筒一样，瞬间出现，不移动）
 pub fn spawn_laser(
     commands: &mut Commands,
@@ -48,6 +59,11 @@ pub fn spawn_laser(
 
     commands.spawn((
         Laser,
+        LaserBeam {
+            start: laser_position.truncate() - params.direction * laser_half_height,
+            direction: params.direction,
+            hit: HashSet::new(),
+        },
         PlayingEntity,
         BulletOwner {
             owner_type: params.owner_type,
@@ -269,4 +285,80 @@ pub fn player_laser_system(
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// 激光命中判定系统
+/// 每帧用 Rapier 形状查询按旋转后的光束矩形判定命中：敌方坦克走统一的击毁结算（每道光束对同一辆坦克只伤害一次），
+/// 子弹走子弹销毁流程，地形标记为待销毁（激光动画结束时化为烟雾）。
+/// 钢铁挡住激光时（没有穿透效果），光束在第一块钢铁处截断，钢铁不受损
+pub fn laser_hit_system(
+    mut commands: Commands,
+    rapier_context: ReadRapierContext,
+    mut lasers: Query<(&mut Transform, &mut Sprite, &BulletOwner, &mut LaserBeam), With<Laser>>,
+    enemies: Query<(), With<EnemyTank>>,
+    bullets: Query<(), With<Bullet>>,
+    steels: Query<(), With<Steel>>,
+    terrain: Query<(), Or<(With<Brick>, With<Steel>, With<Forest>, With<Barrier>, With<Sea>)>>,
+    player_info: Res<PlayerInfo>,
+    mut damage_events: MessageWriter<Damage>,
+) {
+    let Ok(context) = rapier_context.single() else {
+        return;
+    };
+
+    for (mut laser_transform, mut sprite, laser_owner, mut beam) in &mut lasers {
+        let angle = beam.direction.y.atan2(beam.direction.x) - std::f32::consts::FRAC_PI_2;
+
+        // 计算光束长度：钢铁挡住激光时截断到第一块钢铁
+        let has_penetrate = player_info.players.get(&laser_owner.owner_type).is_some_and(|stats| stats.penetrate);
+        let blocked_by_steel = LASER_STOPPED_BY_STEEL && !has_penetrate;
+        let mut beam_length = LASER_LENGTH;
+        if blocked_by_steel {
+            let probe = Collider::cuboid(LASER_HALF_WIDTH, 1.0);
+            let is_steel = |entity: Entity| steels.contains(entity);
+            if let Some((_, hit)) = context.cast_shape(
+                beam.start,
+                angle,
+                beam.direction * LASER_LENGTH,
+                &*probe.raw,
+                ShapeCastOptions::with_max_time_of_impact(1.0),
+                QueryFilter::default().predicate(&is_steel),
+            ) {
+                beam_length = hit.time_of_impact * LASER_LENGTH;
+            }
+        }
+
+        // 被截断的光束按实际长度显示
+        let center = beam.start + beam.direction * beam_length / 2.0;
+        laser_transform.translation.x = center.x;
+        laser_transform.translation.y = center.y;
+        sprite.custom_size = Some(Vec2::new(512.0, beam_length));
+        if beam_length <= 0.0 {
+            continue;
+        }
+
+        // 判定光束矩形内的所有碰撞体（包括传感器）
+        let shape = Collider::cuboid(LASER_HALF_WIDTH, beam_length / 2.0);
+        context.intersect_shape(center, angle, &*shape.raw, QueryFilter::default(), |entity| {
+            if enemies.contains(entity) {
+                // 击中敌方坦克（伤害、击毁和计分统一结算），同一道光束只伤害一次
+                if !beam.hit.insert(entity) {
+                    return true;
+                }
+                damage_events.write(Damage {
+                    target: entity,
+                    amount: 1,
+                    kind: DamageKind::Laser,
+                    source: Some(laser_owner.owner_type),
+                });
+            } else if bullets.contains(entity) {
+                // 子弹被激光烧毁
+                commands.entity(entity).try_insert(BulletDespawnMarker);
+            } else if terrain.contains(entity) && !(blocked_by_steel && steels.contains(entity)) {
+                // 标记地形为待销毁，激光动画结束时化为烟雾
+                commands.entity(entity).try_insert(DespawnMarker);
+            }
+            true
+        });
+    }
+}
@@ -7,6 +7,59 @@ use std::path::Path;
 /// 关卡地图数据（使用 TerrainType 枚举）
 pub type LevelMap = [[TerrainType; crate::map::MAP_COLS]; crate::map::MAP_ROWS];
 
+/// 占领区：从 (row, col) 开始的 rows 行 cols 列格子
+#[derive(Clone, Copy)]
+pub struct LevelZone {
+    pub row: usize,
+    pub col: usize,
+    pub rows: usize,
+    pub cols: usize,
+}
+
+/// 关卡元数据（关卡文件中以 @ 开头的行）
+#[derive(Clone, Default)]
+pub struct LevelMeta {
+    pub par_time: Option<f32>,        // 限时挑战的标准时间（秒）
+    pub gold_time: Option<f32>,       // 限时挑战的金牌时间（秒）
+    pub flag: Option<(usize, usize)>, // 夺旗模式中旗帜所在的格子（行，列）
+    pub zones: Vec<LevelZone>,        // 占山为王模式的占领区（可以有多个）
+}
+
+/// 解析一行元数据：@键 值
+fn parse_meta_line(meta: &mut LevelMeta, line: &str) -> Result<(), String> {
+    let mut parts = line.trim_start_matches('@').split_whitespace();
+    let key = parts.next().unwrap_or_default();
+    let value = parts.next().ok_or_else(|| format!("missing value for @{key}"))?;
+    let parse_seconds = |value: &str| value.parse::<f32>().map_err(|e| format!("invalid time for @{key}: {e}"));
+    match key {
+        "par" => meta.par_time = Some(parse_seconds(value)?),
+        "gold" => meta.gold_time = Some(parse_seconds(value)?),
+        "flag" => {
+            let col = parts.next().ok_or_else(|| format!("missing column for @{key}"))?;
+            let parse_index = |value: &str| value.parse::<usize>().map_err(|e| format!("invalid cell for @{key}: {e}"));
+            let (row, col) = (parse_index(value)?, parse_index(col)?);
+            if row >= crate::map::MAP_ROWS || col >= crate::map::MAP_COLS {
+                return Err(format!("cell ({row}, {col}) for @{key} is outside the map"));
+            }
+            meta.flag = Some((row, col));
+        }
+        "zone" => {
+            let parse_index = |value: &str| value.parse::<usize>().map_err(|e| format!("invalid zone for @{key}: {e}"));
+            let col = parts.next().ok_or_else(|| format!("missing column for @{key}"))?;
+            // 行数和列数可以省略，默认为 1 格
+            let rows = parts.next().map_or(Ok(1), parse_index)?;
+            let cols = parts.next().map_or(Ok(1), parse_index)?;
+            let zone = LevelZone { row: parse_index(value)?, col: parse_index(col)?, rows, cols };
+            if rows == 0 || cols == 0 || zone.row + rows > crate::map::MAP_ROWS || zone.col + cols > crate::map::MAP_COLS {
+                return Err(format!("zone at ({}, {}) of size {}x{} for @{} is outside the map", zone.row, zone.col, rows, cols, key));
+            }
+            meta.zones.push(zone);
+        }
+        _ => return Err(format!("unknown key @{key}")),
+    }
+    Ok(())
+}
+
 /// 从文件加载关卡数据
 /// 地形符号对照：
 /// . = 空地
@@ -23,14 +76,20 @@ pub type LevelMap = [[TerrainType; crate::map::MAP_COLS]; crate::map::MAP_ROWS];
 /// it = 钢铁上半（100×50）
 /// ib = 钢铁下半（100×50）
 /// a = 屏障（可破坏，2发子弹）
-fn load_level_from_file(level_num: usize) -> Result<LevelMap, String> {
+///
+/// 以 @ 开头的行是元数据（不计入地图行数）：
+/// @par 秒数  = 限时挑战的标准时间
+/// @gold 秒数 = 限时挑战的金牌时间
+/// @flag 行 列 = 夺旗模式中旗帜所在的格子（从 0 开始）
+/// @zone 行 列 [行数 列数] = 占山为王模式的占领区，可以写多行
+fn load_level_from_file(level_name: &str) -> Result<(LevelMap, LevelMeta), String> {
     // 检查是否在系统安装目录运行
     let levels_dir = if Path::new("/usr/share/tank-battle/levels").exists() {
         "/usr/share/tank-battle/levels"
     } else {
         "levels"
     };
-    let file_path = format!("{}/{}.txt", levels_dir, level_num);
+    let file_path = format!("{levels_dir}/{level_name}.txt");
     let path = Path::new(&file_path);
 
     if !path.exists() {
@@ -41,8 +100,16 @@ fn load_level_from_file(level_num: usize) -> Result<LevelMap, String> {
         .map_err(|e| format!("Failed to read level file: {}", e))?;
 
     let mut result: LevelMap = [[TerrainType::Empty; crate::map::MAP_COLS]; crate::map::MAP_ROWS];
+    let mut meta = LevelMeta::default();
+
+    let (meta_lines, map_lines): (Vec<&str>, Vec<&str>) = content.lines().partition(|line| line.starts_with('@'));
+    for line in meta_lines {
+        if let Err(e) = parse_meta_line(&mut meta, line) {
+            eprintln!("Warning: Ignoring metadata in {file_path}: {e}");
+        }
+    }
 
-    for (row_idx, line) in content.lines().enumerate() {
+    for (row_idx, line) in map_lines.into_iter().enumerate() {
         if row_idx >= crate::map::MAP_ROWS {
             break;
         }
@@ -59,26 +126,46 @@ fn load_level_from_file(level_num: usize) -> Result<LevelMap, String> {
         }
     }
 
-    Ok(result)
+    Ok((result, meta))
+}
+
+/// 加载指定关卡，如果关卡文件不存在，返回第1关作为默认值
+fn load_level_or_default(level: usize) -> (LevelMap, LevelMeta) {
+    load_level_from_file(&level.to_string()).unwrap_or_else(|_| {
+        // 如果加载失败，尝试加载第1关
+        if level != 1 {
+            eprintln!("Warning: Failed to load level {level}, falling back to level 1");
+            load_level_from_file("1").unwrap_or_else(|_| {
+                // 如果第1关也加载失败，返回空地图
+                ([[TerrainType::Empty; crate::map::MAP_COLS]; crate::map::MAP_ROWS], LevelMeta::default())
+            })
+        } else {
+            eprintln!("Error: Failed to load level 1, using empty map");
+            ([[TerrainType::Empty; crate::map::MAP_COLS]; crate::map::MAP_ROWS], LevelMeta::default())
+        }
+    })
 }
 
 /// 获取指定关卡的地图数据
 /// 如果关卡文件不存在，返回第1关作为默认值
 pub fn get_level(level: usize) -> LevelMap {
-    match load_level_from_file(level) {
-        Ok(map) => map,
-        Err(_) => {
-            // 如果加载失败，尝试加载第1关
-            if level != 1 {
-                eprintln!("Warning: Failed to load level {}, falling back to level 1", level);
-                load_level_from_file(1).unwrap_or_else(|_| {
-                    // 如果第1关也加载失败，返回空地图
-                    [[TerrainType::Empty; crate::map::MAP_COLS]; crate::map::MAP_ROWS]
-                })
-            } else {
-                eprintln!("Error: Failed to load level 1, using empty map");
-                [[TerrainType::Empty; crate::map::MAP_COLS]; crate::map::MAP_ROWS]
-            }
-        }
-    }
+    load_level_or_default(level).0
+}
+
+/// 获取指定关卡的元数据
+/// 如果关卡文件不存在，返回第1关的元数据
+pub fn get_level_meta(level: usize) -> LevelMeta {
+    load_level_or_default(level).1
+}
+
+/// 获取对战模式的地图数据（levels/versus.txt，左右对称）
+/// 如果地图文件不存在，返回空地图
+pub fn get_versus_level() -> LevelMap {
+    load_level_from_file("versus").map_or_else(
+        |e| {
+            eprintln!("Error: Failed to load versus map ({e}), using empty map");
+            [[TerrainType::Empty; crate::map::MAP_COLS]; crate::map::MAP_ROWS]
+        },
+        |(map, _)| map,
+    )
 }
@@ -0,0 +1,204 @@
+//! 生命系统模块
+//!
+//! 玩家除了血条之外还有备用生命：分数每达到一定数值奖励一条，坦克被摧毁后倒计时，
+//! 结束时消耗一条生命在出生点复活，复活后短时间无敌（坦克外面罩着闪烁的护罩）。
+//! 对战和生存模式下不消耗生命，可以无限复活
+
+use bevy::prelude::*;
+
+use crate::constants::{
+    GameOverTimer, Invulnerable, InvulnerableEffect, MAX_LIVES, PlayerAvatar, PlayerDead,
+    PlayerTank, PlayerUI, SOUND_POWERUP, TEXTURE_BUBBLE, TEXTURE_PLAYER_TANK1,
+    TEXTURE_PLAYER_TANK2, TankType,
+};
+use crate::resources::{
+    CommanderLife, GameMode, MatchOptions, PlayerInfo, PlayerRespawnTimers, PlayerStatChanged,
+    StatType,
+};
+use crate::revive::PlayerWreck;
+
+/// 每获得多少分奖励一条生命
+pub const EXTRA_LIFE_SCORE_INTERVAL: usize = 5000;
+/// 坦克被摧毁到复活的时间（秒）
+const RESPAWN_DELAY: f32 = 3.0;
+/// 复活后的无敌时间（秒）
+const RESPAWN_INVULNERABILITY: f32 = 3.0;
+
+/// 奖励生命系统：分数每跨过一个 `EXTRA_LIFE_SCORE_INTERVAL` 奖励一条生命（不超过上限）
+pub fn award_extra_lives(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+) {
+    for (player_type, player_stats) in &mut player_info.players {
+        let earned = player_stats.score / EXTRA_LIFE_SCORE_INTERVAL;
+        if earned <= player_stats.extra_lives_awarded {
+            continue;
+        }
+        let new_lives = earned - player_stats.extra_lives_awarded;
+        player_stats.extra_lives_awarded = earned;
+        player_stats.lives = (player_stats.lives + new_lives).min(MAX_LIVES);
+
+        // 播放道具音效
+        let powerup_sound: Handle<AudioSource> = asset_server.load(SOUND_POWERUP);
+        commands.spawn(AudioPlayer::new(powerup_sound));
+
+        stat_changed_events.write(PlayerStatChanged {
+            player_type: *player_type,
+            stat_type: StatType::Lives,
+        });
+    }
+}
+
+/// 复活系统：坦克被摧毁且还有剩余生命的玩家倒计时结束后在出生点复活
+pub fn respawn_players(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut respawn_timers: ResMut<PlayerRespawnTimers>,
+    commander_life: Res<CommanderLife>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    player_tanks: Query<&PlayerTank>,
+    mut player_avatars: Query<(Entity, &PlayerUI, &mut Sprite), (With<PlayerAvatar>, With<PlayerDead>)>,
+    wrecks: Query<(Entity, &PlayerWreck)>,
+    game_over_timers: Query<(), With<GameOverTimer>>,
+) {
+    // 司令官阵亡或游戏已经结束时不再复活（对战模式的回合结束由 versus 模块处理）
+    let is_versus = *game_mode == GameMode::Versus;
+    let unlimited_lives = match_options.unlimited_lives(*game_mode);
+    if (!is_versus && commander_life.life_red_bar == 0) || !game_over_timers.is_empty() {
+        return;
+    }
+
+    for (player_type, player_stats) in &mut player_info.players {
+        let has_tank = player_tanks.iter().any(|player_tank| player_tank.tank_type == *player_type);
+        if has_tank || player_stats.life_red_bar > 0 || (!unlimited_lives && player_stats.lives == 0) {
+            respawn_timers.timers.remove(player_type);
+            continue;
+        }
+
+        let timer = respawn_timers
+            .timers
+            .entry(*player_type)
+            .or_insert_with(|| Timer::from_seconds(RESPAWN_DELAY, TimerMode::Once));
+        timer.tick(time.delta());
+        if !timer.is_finished() {
+            continue;
+        }
+        respawn_timers.timers.remove(player_type);
+
+        // 消耗一条生命（对战和生存模式不消耗），恢复血条
+        if !unlimited_lives {
+            player_stats.lives -= 1;
+        }
+        player_stats.life_red_bar = 3;
+
+        // 在出生点重新生成坦克，清理残骸，恢复头像
+        let position = crate::player_start_position(*player_type, *game_mode);
+        spawn_revived_tank(&mut commands, &asset_server, &mut texture_atlas_layouts, *player_type, position);
+        for (wreck_entity, wreck) in &wrecks {
+            if wreck.player_type == *player_type {
+                commands.entity(wreck_entity).try_despawn();
+            }
+        }
+        restore_player_avatar(&mut commands, &asset_server, &mut texture_atlas_layouts, &mut player_avatars, *player_type);
+    }
+}
+
+/// 在指定位置重新生成玩家坦克，复活后短时间无敌
+pub fn spawn_revived_tank(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    player_type: TankType,
+    position: Vec3,
+) {
+    let (texture_atlas_layout, animation_indices) = crate::player_tank_atlas(texture_atlas_layouts);
+    let tank_entity = match player_type {
+        TankType::Player1 => crate::spawn_player1_tank(
+            commands,
+            asset_server.load(TEXTURE_PLAYER_TANK1),
+            texture_atlas_layout,
+            animation_indices,
+            position,
+        ),
+        TankType::Player2 => crate::spawn_player2_tank(
+            commands,
+            asset_server.load(TEXTURE_PLAYER_TANK2),
+            texture_atlas_layout,
+            animation_indices,
+            position,
+        ),
+        TankType::Enemy => return,
+    };
+    commands
+        .entity(tank_entity)
+        .insert(Invulnerable(Timer::from_seconds(RESPAWN_INVULNERABILITY, TimerMode::Once)));
+}
+
+/// 把死亡玩家的头像恢复为动画头像
+pub fn restore_player_avatar(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    player_avatars: &mut Query<(Entity, &PlayerUI, &mut Sprite), (With<PlayerAvatar>, With<PlayerDead>)>,
+    player_type: TankType,
+) {
+    for (avatar_entity, player_idx, mut sprite) in player_avatars.iter_mut() {
+        if player_idx.player_type == player_type {
+            *sprite = crate::player_avatar_sprite(asset_server, texture_atlas_layouts);
+            commands.entity(avatar_entity).remove::<PlayerDead>();
+        }
+    }
+}
+
+/// 无敌状态系统：显示闪烁的护罩，计时结束后移除无敌状态和护罩
+pub fn update_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut player_tanks: Query<(Entity, &mut Invulnerable, Option<&Children>), With<PlayerTank>>,
+    mut effects: Query<&mut Sprite, With<InvulnerableEffect>>,
+) {
+    for (entity, mut invulnerable, children) in &mut player_tanks {
+        invulnerable.tick(time.delta());
+
+        let effect = children.and_then(|children| children.iter().find(|child| effects.contains(*child)));
+
+        if invulnerable.is_finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+            if let Some(effect) = effect {
+                commands.entity(effect).try_despawn();
+            }
+            continue;
+        }
+
+        let Some(effect) = effect else {
+            // 护罩使用气垫的气泡贴图，染成金色以示区别
+            let bubble_texture: Handle<Image> = asset_server.load(TEXTURE_BUBBLE);
+            commands.entity(entity).with_children(|parent| {
+                parent.spawn((
+                    Sprite {
+                        image: bubble_texture,
+                        color: Color::srgb(1.0, 0.85, 0.2),
+                        custom_size: Some(Vec2::new(110.0, 110.0)),
+                        ..default()
+                    },
+                    Transform::from_xyz(0.0, 0.0, 1.0), // 在坦克中心
+                    InvulnerableEffect,
+                ));
+            });
+            continue;
+        };
+
+        // 每 0.2 秒闪烁一次
+        if let Ok(mut sprite) = effects.get_mut(effect) {
+            let visible = invulnerable.elapsed_secs() % 0.2 < 0.1;
+            sprite.color.set_alpha(if visible { 1.0 } else { 0.3 });
+        }
+    }
+}
@@ -18,6 +18,21 @@ mod map;
 mod levels;
 mod bullet;
 mod laser;
+mod powerup;
+mod buff;
+mod combat;
+mod missile;
+mod mine;
+mod build;
+mod results;
+mod highscores;
+mod lives;
+mod revive;
+mod versus;
+mod survival;
+mod timeattack;
+mod ctf;
+mod kingofthehill;
 
 use bevy::{
     audio::{AudioPlayer, Volume},
@@ -36,7 +51,6 @@ use crate::constants::RecoilForce;
 use constants::*;
 #[allow(clippy::wildcard_imports)]
 use resources::*;
-use crate::bullet::BulletOwner;
 
 
 
@@ -83,6 +97,9 @@ fn configure_game_resources(app: &mut App) {
     app.init_state::<GameState>()
         .add_message::<PlayerStatChanged>()
         .add_message::<crate::bullet::EffectEvent>()
+        .add_message::<crate::combat::EnemyKilled>()
+        .add_message::<crate::combat::Damage>()
+        .add_message::<crate::bullet::ShellExploded>()
         .init_resource::<BulletTracker>()
         .init_resource::<StartAnimationFrames>()
         .init_resource::<FadingOut>()
@@ -93,7 +110,14 @@ fn configure_game_resources(app: &mut App) {
         .init_resource::<EnemySpawnState>()
         .init_resource::<StageLevel>()
         .init_resource::<PlayerInfo>()
+        .init_resource::<CampaignRun>()
+        .init_resource::<GameRng>()
+        .init_resource::<StageStats>()
+        .init_resource::<StageResultsTimer>()
+        .init_resource::<highscores::NameEntry>()
+        .insert_resource(highscores::load_high_scores())
         .init_resource::<RecallTimers>()
+        .init_resource::<ReviveTimers>()
         .init_resource::<DashTimers>()
         .init_resource::<BlueBarRegenTimer>()
         .init_resource::<CommanderLife>()
@@ -101,17 +125,32 @@ fn configure_game_resources(app: &mut App) {
         .init_resource::<GameEntitiesSpawned>()
         .init_resource::<BarrierDamageTracker>()
         .init_resource::<DashDamageTracker>()
-        .insert_resource(PlayerRespawnTimer(Timer::from_seconds(3.0, TimerMode::Once)))
+        .init_resource::<PowerUpDirector>()
+        .init_resource::<FortificationTimer>()
+        .init_resource::<EnemyFreezeTimer>()
+        .init_resource::<MatchOptions>()
+        .init_resource::<PlayerRespawnTimers>()
+        .init_resource::<VersusMatch>()
+        .init_resource::<SurvivalState>()
+        .init_resource::<CaptureFlagState>()
+        .init_resource::<KingOfTheHillState>()
+        .insert_resource(timeattack::load_time_attack_state())
         .insert_resource(ClearColor(BACKGROUND_COLOR));
 }
 
 fn register_game_systems(app: &mut App) {
     app.add_systems(OnEnter(GameState::StartScreen), (cleanup_playing_entities, spawn_start_screen).chain())
         .add_systems(OnEnter(GameState::FadingOut), setup_fade_out)
-        .add_systems(OnEnter(GameState::StageIntro), (reset_for_next_stage, spawn_stage_intro).chain())
+        .add_systems(OnEnter(GameState::StageIntro), (reset_for_next_stage, timeattack::load_stage_times, kingofthehill::reset_hill_score, spawn_stage_intro).chain())
         .add_systems(Update, handle_stage_intro_timer.run_if(in_state(GameState::StageIntro)))
         .add_systems(OnExit(GameState::StageIntro), despawn_stage_intro)
         .add_systems(OnEnter(GameState::Playing), spawn_game_entities_if_needed)
+        .add_systems(OnEnter(GameState::StageResults), (timeattack::record_stage_time, results::spawn_stage_results).chain())
+        .add_systems(Update, (results::animate_stage_results, results::handle_stage_results_input).run_if(in_state(GameState::StageResults)))
+        .add_systems(OnExit(GameState::StageResults), results::despawn_stage_results)
+        .add_systems(OnEnter(GameState::VersusResults), versus::spawn_versus_results)
+        .add_systems(Update, versus::handle_versus_results_input.run_if(in_state(GameState::VersusResults)))
+        .add_systems(OnExit(GameState::VersusResults), versus::despawn_versus_results)
         .add_systems(OnEnter(GameState::Paused), spawn_pause_ui)
         .add_systems(OnExit(GameState::Paused), ( despawn_pause_ui,))
         .add_systems(OnEnter(GameState::GameOver), spawn_game_over_ui)
@@ -122,8 +161,14 @@ fn register_game_systems(app: &mut App) {
         .add_systems(OnEnter(GameState::Credits), (cleanup_start_screen_ui, spawn_credits_screen).chain())
         .add_systems(OnExit(GameState::Credits), (despawn_credits_screen, spawn_start_screen).chain())
         .add_systems(Update, handle_credits_input.run_if(in_state(GameState::Credits)))
+        .add_systems(OnEnter(GameState::HighScores), (cleanup_start_screen_ui, highscores::spawn_high_scores_screen).chain())
+        .add_systems(OnExit(GameState::HighScores), (highscores::despawn_high_scores_screen, spawn_start_screen).chain())
+        .add_systems(Update, highscores::handle_high_scores_input.run_if(in_state(GameState::HighScores)))
+        .add_systems(OnEnter(GameState::NameEntry), highscores::spawn_name_entry)
+        .add_systems(Update, (highscores::handle_name_entry_input, highscores::update_name_entry_ui).chain().run_if(in_state(GameState::NameEntry)))
+        .add_systems(OnExit(GameState::NameEntry), highscores::despawn_name_entry)
         .add_systems(Startup, setup)
-        .add_systems(Update, (move_enemy_tanks).chain().run_if(in_state(GameState::Playing)))
+        .add_systems(Update, (ctf::hunt_flag_carrier, move_enemy_tanks).chain().run_if(in_state(GameState::Playing)))
         .add_systems(Update, enemy_spawn_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, move_player_tank.run_if(in_state(GameState::Playing)))
         .add_systems(Update, animate_player_tank_texture.run_if(in_state(GameState::Playing)))
@@ -145,16 +190,35 @@ fn register_game_systems(app: &mut App) {
         .add_systems(Update, animate_enemy_born_animation.run_if(in_state(GameState::Playing)))
         .add_systems(Update, handle_game_over_delay.run_if(in_state(GameState::Playing)))
         .add_systems(Update, check_game_over.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, lives::award_extra_lives.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, lives::respawn_players.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, lives::update_invulnerability.run_if(in_state(GameState::Playing)))
         .add_systems(Update, bullet::enemy_shoot_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, bullet::player_shoot_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, bullet::bullet_bounds_check_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, bullet::bullet_despawn_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, bullet::bullet_terrain_collision_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, bullet::bullet_tank_collision_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, bullet::bullet_bullet_collision_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, bullet::update_ricochet_rotation.run_if(in_state(GameState::Playing)))
         .add_systems(Update, bullet::bullet_commander_collision_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, bullet::handle_effect_events.run_if(in_state(GameState::Playing)))
         .add_systems(Update, laser::player_laser_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, missile::player_missile_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, missile::missile_homing_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, mine::player_mine_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, mine::update_mine_arming.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, mine::mine_trigger_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, build::player_build_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, handle_powerup_collision.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, buff::update_buffs.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, combat::resolve_shell_explosions.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, combat::resolve_enemy_kills.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, (combat::sync_health_components, combat::resolve_damage).chain().run_if(in_state(GameState::Playing)))
+        .add_systems(Update, update_frozen_enemies.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, powerup::update_fortification.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, powerup::powerup_director_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, powerup::update_powerup_lifetime.run_if(in_state(GameState::Playing)))
         .add_systems(Update, update_air_cushion_effect.run_if(in_state(GameState::Playing)))
         .add_systems(Update, handle_stat_changed_for_blink.run_if(in_state(GameState::Playing)))
         .add_systems(Update, update_player_info_display.run_if(in_state(GameState::Playing)))
@@ -162,10 +226,20 @@ fn register_game_systems(app: &mut App) {
         .add_systems(Update, update_commander_health_bar.run_if(in_state(GameState::Playing)))
         .add_systems(Update, update_enemy_count_display.run_if(in_state(GameState::Playing)))
         .add_systems(Update, check_stage_complete.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, versus::check_round_over.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, survival::update_survival_waves.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, (ctf::update_flag, ctf::update_flag_status_text).chain().run_if(in_state(GameState::Playing)))
+        .add_systems(Update, (kingofthehill::update_control_zones, kingofthehill::update_hill_score_text).chain().run_if(in_state(GameState::Playing)))
+        .add_systems(Update, versus::update_versus_base_health_bars.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, results::track_stage_time.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, timeattack::update_stage_timer_text.run_if(in_state(GameState::Playing)))
+        // 限时挑战重开本关后在下一帧重新生成游戏实体
+        .add_systems(Update, (timeattack::handle_time_attack_restart, spawn_game_entities_if_needed).chain().run_if(in_state(GameState::Playing)))
         // .add_systems(Update, check_bullet_commander_collision.run_if(in_state(GameState::Playing)))
         .add_systems(Update, animate_start_screen.run_if(not(in_state(GameState::Playing))))
         .add_systems(Update, (
             handle_start_screen_input,
+            handle_match_options_input,
             update_option_colors,
         ).run_if(in_state(GameState::StartScreen)))
         .add_systems(Update, update_menu_blink.run_if(in_state(GameState::FadingOut).or(in_state(GameState::StartScreen))))
@@ -177,6 +251,10 @@ fn register_game_systems(app: &mut App) {
             handle_recall_input,
             update_recall_timers,
         ).run_if(in_state(GameState::Playing)))
+        .add_systems(Update, (
+            revive::handle_revive_input,
+            revive::update_revive_timers,
+        ).chain().run_if(in_state(GameState::Playing)))
         .add_systems(Update, handle_dash_input.run_if(in_state(GameState::Playing)))
         .add_systems(Update, update_dash_movement.run_if(in_state(GameState::Playing)))
         .add_systems(Update, handle_dash_collision.run_if(in_state(GameState::Playing)))
@@ -185,7 +263,7 @@ fn register_game_systems(app: &mut App) {
         .add_systems(Update, handle_recoil_force.run_if(in_state(GameState::Playing)))
         .add_systems(Update, animate_laser.run_if(in_state(GameState::Playing)))
         .add_systems(Update, animate_smoke.run_if(in_state(GameState::Playing)))
-        .add_systems(Update, laser_collision_system.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, laser::laser_hit_system.run_if(in_state(GameState::Playing)))
         .add_systems(Update, fade_out_screen.run_if(in_state(GameState::FadingOut)));
 }
 
@@ -301,6 +379,20 @@ fn spawn_start_screen_title(
         Transform::from_xyz(0.0, 400.0, 1.0),
     ));
 
+    // 对局选项（对局规则、友军误伤开关、对战局数和中立敌人），文本由 handle_match_options_input 更新
+    commands.spawn((
+        StartScreenUI,
+        MatchOptionsText,
+        Text2d::default(),
+        TextFont {
+            font_size: 24.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 0.0)), // 黄色
+        Transform::from_xyz(0.0, 200.0, 1.0),
+    ));
+
     // 1 Player 选项
     commands.spawn((
         StartScreenUI,
@@ -311,7 +403,7 @@ fn spawn_start_screen_title(
             ..default()
         },
         TextColor(Color::srgb(1.0, 1.0, 0.0)), // 初始选中，黄色
-        Transform::from_xyz(0.0, 50.0, 1.0),
+        Transform::from_xyz(0.0, 130.0, 1.0),
         MenuOption { index: 0 },
     ));
 
@@ -325,10 +417,24 @@ fn spawn_start_screen_title(
             ..default()
         },
         TextColor(Color::srgb(1.0, 1.0, 1.0)), // 白色
-        Transform::from_xyz(0.0, -50.0, 1.0),
+        Transform::from_xyz(0.0, 50.0, 1.0),
         MenuOption { index: 1 },
     ));
 
+    // Versus 选项
+    commands.spawn((
+        StartScreenUI,
+        Text2d("Versus".to_string()),
+        TextFont {
+            font_size: 80.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)), // 白色
+        Transform::from_xyz(0.0, -30.0, 1.0),
+        MenuOption { index: 2 },
+    ));
+
     // About 选项
     commands.spawn((
         StartScreenUI,
@@ -339,8 +445,8 @@ fn spawn_start_screen_title(
             ..default()
         },
         TextColor(Color::srgb(1.0, 1.0, 1.0)), // 白色
-        Transform::from_xyz(0.0, -150.0, 1.0),
-        MenuOption { index: 2 },
+        Transform::from_xyz(0.0, -110.0, 1.0),
+        MenuOption { index: 3 },
     ));
 
     // Credits 选项
@@ -353,8 +459,22 @@ fn spawn_start_screen_title(
             ..default()
         },
         TextColor(Color::srgb(1.0, 1.0, 1.0)), // 白色
-        Transform::from_xyz(0.0, -250.0, 1.0),
-        MenuOption { index: 3 },
+        Transform::from_xyz(0.0, -190.0, 1.0),
+        MenuOption { index: 4 },
+    ));
+
+    // High Scores 选项
+    commands.spawn((
+        StartScreenUI,
+        Text2d("High Scores".to_string()),
+        TextFont {
+            font_size: 80.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)), // 白色
+        Transform::from_xyz(0.0, -270.0, 1.0),
+        MenuOption { index: 5 },
     ));
 
     // EXIT 选项
@@ -368,7 +488,7 @@ fn spawn_start_screen_title(
         },
         TextColor(Color::srgb(1.0, 1.0, 1.0)), // 白色
         Transform::from_xyz(0.0, -350.0, 1.0),
-        MenuOption { index: 4 },
+        MenuOption { index: 6 },
     ));
 }
 
@@ -376,7 +496,7 @@ fn spawn_start_screen_instructions(commands: &mut Commands, font: &Handle<Font>)
     // 玩家1操作说明
     commands.spawn((
         StartScreenUI,
-        Text2d("Player 1 (Li Yun Long): WASD to move | J to shoot | I to recall | K to dash | L to laser".to_string()),
+        Text2d("Player 1 (Li Yun Long): WASD to move | J to shoot | U to missile | O to mine | H to build | I to recall | K to dash | L to laser".to_string()),
         TextFont {
             font_size: 24.0,
             font: font.clone(),
@@ -390,7 +510,7 @@ fn spawn_start_screen_instructions(commands: &mut Commands, font: &Handle<Font>)
     // 玩家2操作说明
     commands.spawn((
         StartScreenUI,
-        Text2d("Player 2 (Chu Yun Fei): Arrow Keys to move | 1 to shoot | 4 to recall | 2 to dash | 3 to laser".to_string()),
+        Text2d("Player 2 (Chu Yun Fei): Arrow Keys to move | 1 to shoot | 5 to missile | 6 to mine | 7 to build | 4 to recall | 2 to dash | 3 to laser".to_string()),
         TextFont {
             font_size: 24.0,
             font: font.clone(),
@@ -771,12 +891,10 @@ fn spawn_map_terrain(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
-    stage_level: usize,
+    level_map: &crate::levels::LevelMap,
 ) {
     use crate::map::{TerrainType, grid_to_world, MAP_ROWS, MAP_COLS};
 
-    let level_map = crate::levels::get_level(stage_level);
-
     for row in 0..MAP_ROWS {
         for col in 0..MAP_COLS {
             let terrain = level_map[row][col];
@@ -1120,6 +1238,7 @@ fn spawn_commander(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    owner: Option<TankType>,
 ) {
     let commander_texture: Handle<Image> = asset_server.load(TEXTURE_COMMANDER);
     // commander.png 实际尺寸: 1400x1200, 每帧 140x120, 10列 x 10行, 共100帧
@@ -1128,79 +1247,27 @@ fn spawn_commander(
     let commander_texture_atlas_layout = texture_atlas_layouts.add(commander_texture_atlas);
     let commander_animation_indices = AnimationIndices { first: 0, last: 99 };
 
-    let commander_y = MAP_BOTTOM_Y + COMMANDER_HEIGHT / 2.0;
-    let commander_x = 0.0;
+    // 合作模式的司令官在底部中间，对战模式的司令官在各自一方的左右两端
+    let (commander_position, wall_positions) = owner.map_or_else(
+        || (Vec3::new(0.0, MAP_BOTTOM_Y + COMMANDER_HEIGHT / 2.0, 0.0), powerup::commander_wall_positions()),
+        |owner| {
+            let position = if owner == TankType::Player1 { VERSUS_COMMANDER1_POSITION } else { VERSUS_COMMANDER2_POSITION };
+            (position, powerup::versus_commander_wall_positions(position))
+        },
+    );
+    let commander_x = commander_position.x;
+    let commander_y = commander_position.y;
 
     // 创建包围司令官的砖块堡垒墙
-    let brick_texture: Handle<Image> = asset_server.load(TEXTURE_BRICK);
-    let brick_size = 50.0; // 每块砖的大小
-
-    // 司令官边界
-    let commander_left = -COMMANDER_WIDTH / 2.0;
-    let commander_right = COMMANDER_WIDTH / 2.0;
-    let commander_top = commander_y + COMMANDER_HEIGHT / 2.0;
-    let commander_bottom = commander_y - COMMANDER_HEIGHT / 2.0;
-
-    // 左墙：3块砖，紧贴司令官左侧
-    for i in 0..3 {
-        let y = commander_bottom + brick_size / 2.0 + i as f32 * brick_size;
-        commands.spawn((
-            Brick,
-            PlayingEntity,
-            Sprite {
-                image: brick_texture.clone(),
-                custom_size: Some(Vec2::new(brick_size, brick_size)),
-                ..default()
-            },
-            Transform::from_xyz(commander_left - brick_size / 2.0, y, 0.0),
-            RigidBody::Fixed,
-            Collider::cuboid(brick_size / 2.0, brick_size / 2.0),
-            ActiveEvents::COLLISION_EVENTS,
-            ActiveCollisionTypes::all(),
-        ));
-    }
-
-    // 右墙：3块砖，紧贴司令官右侧
-    for i in 0..3 {
-        let y = commander_bottom + brick_size / 2.0 + i as f32 * brick_size;
-        commands.spawn((
-            Brick,
-            PlayingEntity,
-            Sprite {
-                image: brick_texture.clone(),
-                custom_size: Some(Vec2::new(brick_size, brick_size)),
-                ..default()
-            },
-            Transform::from_xyz(commander_right + brick_size / 2.0, y, 0.0),
-            RigidBody::Fixed,
-            Collider::cuboid(brick_size / 2.0, brick_size / 2.0),
-            ActiveEvents::COLLISION_EVENTS,
-            ActiveCollisionTypes::all(),
-        ));
+    for position in wall_positions {
+        powerup::spawn_commander_wall_block(commands, asset_server, position, false);
     }
 
-    // 上墙：2块砖封顶，紧贴司令官顶部
-    for i in 0..2 {
-        let x = -brick_size / 2.0 + i as f32 * brick_size;
-        commands.spawn((
-            Brick,
-            PlayingEntity,
-            Sprite {
-                image: brick_texture.clone(),
-                custom_size: Some(Vec2::new(brick_size, brick_size)),
-                ..default()
-            },
-            Transform::from_xyz(x, commander_top + brick_size / 2.0, 0.0),
-            RigidBody::Fixed,
-            Collider::cuboid(brick_size / 2.0, brick_size / 2.0),
-            ActiveEvents::COLLISION_EVENTS,
-            ActiveCollisionTypes::all(),
-        ));
-    }
-
-    commands.spawn((
+    let mut commander = commands.spawn((
         Commander,
         PlayingEntity,
+        Health::new(3),
+        Armor::default(),
         Sprite {
             image: commander_texture,
             texture_atlas: Some(TextureAtlas {
@@ -1218,6 +1285,9 @@ fn spawn_commander(
         Collider::cuboid(COMMANDER_WIDTH / 2.0, COMMANDER_HEIGHT / 2.0),
         ActiveEvents::COLLISION_EVENTS,
     ));
+    if let Some(owner) = owner {
+        commander.insert(CommanderOwner(owner));
+    }
 
     // 创建音乐动画精灵（一直播放）
     let music_texture: Handle<Image> = asset_server.load(TEXTURE_MUSIC_NOTE);
@@ -1245,11 +1315,29 @@ fn spawn_commander(
     ));
 }
 
+// 玩家的出生位置（对战模式在各自堡垒上方）
+const fn player_start_position(tank_type: TankType, game_mode: GameMode) -> Vec3 {
+    match (game_mode, tank_type) {
+        (GameMode::Versus, TankType::Player2) => VERSUS_PLAYER2_START_POSITION,
+        (GameMode::Versus, _) => VERSUS_PLAYER1_START_POSITION,
+        (_, TankType::Player2) => PLAYER2_START_POSITION,
+        _ => PLAYER1_START_POSITION,
+    }
+}
+
+// 玩家坦克精灵图的布局和动画帧
+fn player_tank_atlas(texture_atlas_layouts: &mut Assets<TextureAtlasLayout>) -> (Handle<TextureAtlasLayout>, AnimationIndices) {
+    let player_tile_size = UVec2::new(293, 328);
+    let player_texture_atlas = TextureAtlasLayout::from_grid(player_tile_size, 2, 1, None, None);
+    (texture_atlas_layouts.add(player_texture_atlas), AnimationIndices { first: 0, last: 1 })
+}
+
 fn spawn_player1_tank(
     commands: &mut Commands,
     texture: Handle<Image>,
     texture_atlas_layout: Handle<TextureAtlasLayout>,
     animation_indices: AnimationIndices,
+    position: Vec3,
 ) -> Entity {
     let player_tank = PlayerTank { tank_type: TankType::Player1 };
 
@@ -1259,6 +1347,8 @@ fn spawn_player1_tank(
         .insert(player_tank)
         .insert(PlayingEntity)
         .insert(TankFireConfig::default())
+        .insert(Health::new(3))
+        .insert(Armor::default())
         .insert(RotationTimer(Timer::from_seconds(0.1, TimerMode::Once)))
         .insert(TargetRotation { angle: 0.0_f32.to_radians() })
         .insert(Sprite {
@@ -1270,7 +1360,7 @@ fn spawn_player1_tank(
             custom_size: Some(Vec2::new(80.0, 90.0)),
             ..default()
         })
-        .insert(Transform::from_xyz(-TANK_WIDTH / 2.0 - COMMANDER_WIDTH/2.0 - 50.0, MAP_BOTTOM_Y+TANK_HEIGHT / 2.0, 0.0))
+        .insert(Transform::from_translation(position))
         .insert(Velocity{ linvel: Vec2::default(), angvel: 0.0 })
         .insert(animation_indices)
         .insert(AnimationTimer(Timer::from_seconds(0.05, TimerMode::Repeating)))
@@ -1287,11 +1377,53 @@ fn spawn_player1_tank(
         .id()
 }
 
+fn spawn_player2_tank(
+    commands: &mut Commands,
+    texture: Handle<Image>,
+    texture_atlas_layout: Handle<TextureAtlasLayout>,
+    animation_indices: AnimationIndices,
+    position: Vec3,
+) -> Entity {
+    commands.spawn_empty()
+        .insert(PlayerTank { tank_type: TankType::Player2 })
+        .insert(PlayingEntity)
+        .insert(TankFireConfig::default())
+        .insert(Health::new(3))
+        .insert(Armor::default())
+        .insert(RotationTimer(Timer::from_seconds(0.1, TimerMode::Once)))
+        .insert(TargetRotation { angle: 0.0_f32.to_radians() })
+        .insert(Sprite {
+            image: texture,
+            texture_atlas: Some(TextureAtlas {
+                layout: texture_atlas_layout,
+                index: animation_indices.first,
+            }),
+            custom_size: Some(Vec2::new(80.0, 90.0)),
+            ..default()
+        })
+        .insert(Transform::from_translation(position))
+        .insert(Velocity{ linvel: Vec2::default(), angvel: 0.0 })
+        .insert(animation_indices)
+        .insert(AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)))
+        .insert(RigidBody::KinematicPositionBased)
+        .insert(Collider::cuboid(TANK_WIDTH/2.0, TANK_HEIGHT/2.0))
+        .insert(ActiveEvents::COLLISION_EVENTS)
+        .insert(ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_STATIC | ActiveCollisionTypes::KINEMATIC_KINEMATIC)
+        .insert(LockedAxes::ROTATION_LOCKED)
+        .insert(KinematicCharacterController {
+            offset: CharacterLength::Absolute(0.01),
+            filter_groups: None,
+            ..default()
+        })
+        .id()
+}
+
 pub fn spawn_enemy_born_animation(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
     texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
     position: Vec3,
+    class: EnemyClass,
 ) -> Entity {
     let enemy_born_texture: Handle<Image> = asset_server.load(TEXTURE_ENEMY_BORN);
     let enemy_born_tile_size = UVec2::new(192, 192);
@@ -1316,6 +1448,7 @@ pub fn spawn_enemy_born_animation(
         AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
         CurrentAnimationFrame(0),
         BornPosition(position), // 记录出生位置
+        class,                  // 即将出生的敌方坦克类型
     )).id()
 }
 
@@ -1355,12 +1488,13 @@ fn spawn_top_text_info(
     commands: &mut Commands,
     font: &Handle<Font>,
     stage_level: usize,
+    rule: MatchRule,
 ) {
     // 其他游戏信息 UI 元素配置
     let commander_text_x = WINDOW_LEFT_X + 435.0; // 往左平移30像素
 
-    // 关卡信息显示在顶部中心
-    commands.spawn((
+    // 关卡信息显示在顶部中心（生存模式显示当前波次）
+    let mut stage_text = commands.spawn((
         PlayingEntity,
         Text2d(format!("Stage {stage_level}")),
         TextFont {
@@ -1371,6 +1505,45 @@ fn spawn_top_text_info(
         TextColor(Color::srgb(1.0, 1.0, 0.0)), // 黄色
         Transform::from_xyz(0.0, WINDOW_TOP_Y - 50.0, 1.0),
     ));
+    if rule == MatchRule::Survival {
+        stage_text.insert(survival::SurvivalWaveText);
+    }
+
+    // 限时挑战在关卡信息下方显示本关用时
+    if rule == MatchRule::TimeAttack {
+        commands.spawn((
+            PlayingEntity,
+            timeattack::StageTimerText,
+            Text2d::default(),
+            TextFont {
+                font_size: 24.0,
+                font: font.clone(),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            Transform::from_xyz(0.0, WINDOW_TOP_Y - 85.0, 1.0),
+        ));
+    }
+
+    // 夺旗模式在关卡信息下方显示夺旗进度和旗帜状态，占山为王模式显示控制分
+    if matches!(rule, MatchRule::CaptureFlag | MatchRule::KingOfTheHill) {
+        let mut status_text = commands.spawn((
+            PlayingEntity,
+            Text2d::default(),
+            TextFont {
+                font_size: 24.0,
+                font: font.clone(),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            Transform::from_xyz(0.0, WINDOW_TOP_Y - 85.0, 1.0),
+        ));
+        if rule == MatchRule::CaptureFlag {
+            status_text.insert(ctf::FlagStatusText);
+        } else {
+            status_text.insert(kingofthehill::HillScoreText);
+        }
+    }
 
     commands.spawn((
         PlayingEntity,
@@ -1409,6 +1582,22 @@ fn spawn_top_text_info(
     ));
 }
 
+// 玩家头像的动画精灵（死亡后复活时也用它恢复头像）
+fn player_avatar_sprite(asset_server: &AssetServer, texture_atlas_layouts: &mut Assets<TextureAtlasLayout>) -> Sprite {
+    let player_avatar_texture: Handle<Image> = asset_server.load(TEXTURE_AVATAR);
+    let player_avatar_tile_size = UVec2::new(160, 147);
+    let player_avatar_texture_atlas = TextureAtlasLayout::from_grid(player_avatar_tile_size, 13, 3, None, None);
+    Sprite {
+        image: player_avatar_texture,
+        texture_atlas: Some(TextureAtlas {
+            layout: texture_atlas_layouts.add(player_avatar_texture_atlas),
+            index: 0,
+        }),
+        custom_size: Some(Vec2::new(160.0, 147.0)),
+        ..default()
+    }
+}
+
 fn spawn_ui_element_from_config(
     commands: &mut Commands,
     font: &Handle<Font>,
@@ -1443,24 +1632,12 @@ fn spawn_ui_element_from_config(
             ));
         }
         UIElementType::PlayerAvatar => {
-            let player_avatar_texture: Handle<Image> = asset_server.load(TEXTURE_AVATAR);
-            let player_avatar_tile_size = UVec2::new(160, 147);
-            let player_avatar_texture_atlas = TextureAtlasLayout::from_grid(player_avatar_tile_size, 13, 3, None, None);
-            let player_avatar_texture_atlas_layout = texture_atlas_layouts.add(player_avatar_texture_atlas);
             let player_avatar_animation_indices = AnimationIndices { first: 0, last: 32 };
             commands.spawn((
                 PlayerUI { player_type: tank_type },
                 PlayerAvatar,
                 PlayingEntity,
-                Sprite {
-                    image: player_avatar_texture,
-                    texture_atlas: Some(TextureAtlas {
-                        layout: player_avatar_texture_atlas_layout,
-                        index: 0,
-                    }),
-                    custom_size: Some(Vec2::new(160.0, 147.0)),
-                    ..default()
-                },
+                player_avatar_sprite(asset_server, texture_atlas_layouts),
                 Transform::from_xyz(config.x_pos, config.y_pos, 1.0),
                 player_avatar_animation_indices,
                 AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
@@ -1498,91 +1675,23 @@ fn spawn_ui_element_from_config(
     }
 }
 
-fn spawn_power_ups(commands: &mut Commands, asset_server: &AssetServer, texture_atlas_layouts: &mut Assets<TextureAtlasLayout>, stage_level: &StageLevel) {
-    let powerup_type = if stage_level.0 == 1 {
-        // 第一关强制生成 air_cushion 道具
-        PowerUp::AirCushion
-    } else {
-        // 其他关卡随机选择一个道具类型
-        let powerup_types = [
-            PowerUp::SpeedUp,
-            PowerUp::Protection,
-            PowerUp::FireSpeed,
-            PowerUp::FireShell,
-            PowerUp::TrackChain,
-            PowerUp::Penetrate,
-            PowerUp::Repair,
-            PowerUp::Hamburger,
-            PowerUp::AirCushion,
-            PowerUp::Shell,
-        ];
-
-        let mut rng = rand::rng();
-        powerup_types[rng.random_range(0..powerup_types.len())]
-    };
-
-    // 定义禁止区域
-    // 上方：坦克高度区域（MAP_TOP_Y - TANK_HEIGHT 到 MAP_TOP_Y）
-    // 下方：commander高度区域（MAP_BOTTOM_Y 到 MAP_BOTTOM_Y + COMMANDER_HEIGHT）
-    let top_forbidden_y = MAP_TOP_Y - TANK_HEIGHT;
-    let bottom_forbidden_y = MAP_BOTTOM_Y + COMMANDER_HEIGHT;
-
-    // 在随机位置生成道具（在地图范围内），避开禁止区域
-    let mut rng = rand::rng();
-    let x = rng.random_range(MAP_LEFT_X + 100.0..MAP_RIGHT_X - 100.0);
-    let y = rng.random_range(bottom_forbidden_y + 100.0..top_forbidden_y - 100.0);
-    let position = Vec3::new(x, y, 0.0);
-
-    spawn_powerup_batch(commands, asset_server, texture_atlas_layouts, powerup_type, powerup_type.texture_path(), &[position]);
-}
-
-fn spawn_powerup_batch(
-    commands: &mut Commands,
-    asset_server: &AssetServer,
-    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
-    powerup_type: PowerUp,
-    texture_path: &'static str,
-    positions: &[Vec3],
-) {
-    let texture: Handle<Image> = asset_server.load(texture_path);
-    let tile_size = UVec2::new(87, 69);
-    let texture_atlas = TextureAtlasLayout::from_grid(tile_size, 3, 1, None, None);
-    let texture_atlas_layout = texture_atlas_layouts.add(texture_atlas);
-    let animation_indices = AnimationIndices { first: 0, last: 2 };
-
-    for pos in positions {
-        commands.spawn((
-            powerup_type,
-            PlayingEntity,
-            Sprite::from_atlas_image(
-                texture.clone(),
-                TextureAtlas {
-                    layout: texture_atlas_layout.clone(),
-                    index: animation_indices.first,
-                }
-            ),
-            Transform::from_xyz(pos.x, pos.y, 0.8), // z=0.8 使道具高于除了树之外的所有图层
-            animation_indices,
-            AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
-            CurrentAnimationFrame(0),
-            RigidBody::Fixed,
-            Collider::cuboid(87.0 / 2.0, 69.0 / 2.0),
-            Sensor,
-            ActiveEvents::COLLISION_EVENTS,
-        ));
-    }
-}
-
 fn spawn_game_entities_if_needed(
     mut commands: Commands,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     asset_server: Res<AssetServer>,
     mut clear_color: ResMut<ClearColor>,
-    _enemy_spawn_state: Res<EnemySpawnState>,
+    mut enemy_spawn_state: ResMut<EnemySpawnState>,
     mut player_info: ResMut<PlayerInfo>,
     stage_level: Res<StageLevel>,
     game_mode: Res<GameMode>,
     mut entities_spawned: ResMut<GameEntitiesSpawned>,
+    mut powerup_director: ResMut<PowerUpDirector>,
+    (mut fortification, mut enemy_freeze): (ResMut<FortificationTimer>, ResMut<EnemyFreezeTimer>), // 系统参数已达上限，合并为一个参数
+    campaign_run: Res<CampaignRun>,
+    match_options: Res<MatchOptions>,
+    versus_match: Res<VersusMatch>,
+    mut survival_state: ResMut<SurvivalState>,
+    mut capture_flag_state: ResMut<CaptureFlagState>,
 ) {
     // 如果游戏实体已经生成，则跳过
     if entities_spawned.0 {
@@ -1598,19 +1707,20 @@ fn spawn_game_entities_if_needed(
     // 生成墙壁
     spawn_walls(&mut commands);
 
-    // 根据地图数组生成地形
-    spawn_map_terrain(&mut commands, &asset_server, &mut texture_atlas_layouts, stage_level.0);
-
-    // 生成司令官
-    spawn_commander(&mut commands, &asset_server, &mut texture_atlas_layouts);
+    // 根据地图数组生成地形，生成司令官（对战模式使用对称地图，每名玩家一个司令官）
+    if *game_mode == GameMode::Versus {
+        spawn_map_terrain(&mut commands, &asset_server, &mut texture_atlas_layouts, &crate::levels::get_versus_level());
+        spawn_commander(&mut commands, &asset_server, &mut texture_atlas_layouts, Some(TankType::Player1));
+        spawn_commander(&mut commands, &asset_server, &mut texture_atlas_layouts, Some(TankType::Player2));
+    } else {
+        spawn_map_terrain(&mut commands, &asset_server, &mut texture_atlas_layouts, &crate::levels::get_level(stage_level.0));
+        spawn_commander(&mut commands, &asset_server, &mut texture_atlas_layouts, None);
+    }
 
     // 加载玩家坦克纹理和创建精灵图
     let player1_texture = asset_server.load(TEXTURE_PLAYER_TANK1);
     let player2_texture = asset_server.load(TEXTURE_PLAYER_TANK2);
-    let player_tile_size = UVec2::new(293, 328);
-    let player_texture_atlas = TextureAtlasLayout::from_grid(player_tile_size, 2, 1, None, None);
-    let player_texture_atlas_layout = texture_atlas_layouts.add(player_texture_atlas);
-    let player_animation_indices = AnimationIndices { first: 0, last: 1 };
+    let (player_texture_atlas_layout, player_animation_indices) = player_tank_atlas(&mut texture_atlas_layouts);
 
     // 根据游戏模式生成玩家
 
@@ -1630,6 +1740,8 @@ fn spawn_game_entities_if_needed(
 
                     player_animation_indices,
 
+                    player_start_position(TankType::Player1, *game_mode),
+
                 );
 
     
@@ -1675,6 +1787,10 @@ fn spawn_game_entities_if_needed(
     
 
                                     fire_shell: false,
+                                    ricochet: false,
+                                    missiles: MISSILE_START_AMMO,
+                                    lives: STARTING_LIVES,
+                                    extra_lives_awarded: 0,
 
     
 
@@ -1685,6 +1801,7 @@ fn spawn_game_entities_if_needed(
                                     energy_blue_bar: 3,
 
                     score: 0,
+                    buffs: Vec::new(),
 
                 });
 
@@ -1692,9 +1809,9 @@ fn spawn_game_entities_if_needed(
 
                 }
 
-            GameMode::TwoPlayers => {
+            GameMode::TwoPlayers | GameMode::Versus => {
 
-                // 双人模式：生成玩家1和玩家2
+                // 双人模式和对战模式：生成玩家1和玩家2
 
                 let _player1_tank_entity = spawn_player1_tank(
 
@@ -1706,61 +1823,25 @@ fn spawn_game_entities_if_needed(
 
                     player_animation_indices,
 
-                );
-
-
-
-                let _player2_tank_entity = commands.spawn_empty()
-
-                    .insert(PlayerTank { tank_type: TankType::Player2 })
-
-                    .insert(PlayingEntity)
-
-                    .insert(TankFireConfig::default())
-
-                    .insert(RotationTimer(Timer::from_seconds(0.1, TimerMode::Once)))
-
-                    .insert(TargetRotation { angle: 0.0_f32.to_radians() })
-
-                    .insert(Sprite {
-                        image: player2_texture,
-                        texture_atlas: Some(TextureAtlas {
-                            layout: player_texture_atlas_layout,
-                            index: player_animation_indices.first,
-                        }),
-                        custom_size: Some(Vec2::new(80.0, 90.0)),
-                        ..default()
-                    })
-
-                    .insert(Transform::from_xyz(TANK_WIDTH / 2.0 + COMMANDER_WIDTH/2.0 + 50.0, MAP_BOTTOM_Y+TANK_HEIGHT / 2.0, 0.0))
-
-                    .insert(Velocity{ linvel: Vec2::default(), angvel: 0.0 })
-
-                    .insert(player_animation_indices)
-
-                    .insert(AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)))
+                    player_start_position(TankType::Player1, *game_mode),
 
-                    .insert(RigidBody::KinematicPositionBased)
-
-                                        .insert(Collider::cuboid(TANK_WIDTH/2.0, TANK_HEIGHT/2.0))
+                );
 
-                                        .insert(ActiveEvents::COLLISION_EVENTS)
 
-                                        .insert(ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_STATIC | ActiveCollisionTypes::KINEMATIC_KINEMATIC)
 
-                                        .insert(LockedAxes::ROTATION_LOCKED)
+                let _player2_tank_entity = spawn_player2_tank(
 
-                                        .insert(KinematicCharacterController {
+                    &mut commands,
 
-                                            offset: CharacterLength::Absolute(0.01),
+                    player2_texture,
 
-                                            filter_groups: None,
+                    player_texture_atlas_layout,
 
-                                            ..default()
+                    player_animation_indices,
 
-                                        })
+                    player_start_position(TankType::Player2, *game_mode),
 
-                                        .id();
+                );
 
     
 
@@ -1785,12 +1866,17 @@ fn spawn_game_entities_if_needed(
                     air_cushion: false,
 
                     fire_shell: false,
+                    ricochet: false,
+                    missiles: MISSILE_START_AMMO,
+                    lives: STARTING_LIVES,
+                    extra_lives_awarded: 0,
 
                     life_red_bar: 3,
 
                     energy_blue_bar: 3,
 
                     score: 0,
+                    buffs: Vec::new(),
 
                 });
 
@@ -1817,18 +1903,38 @@ fn spawn_game_entities_if_needed(
                     air_cushion: false,
 
                     fire_shell: false,
+                    ricochet: false,
+                    missiles: MISSILE_START_AMMO,
+                    lives: STARTING_LIVES,
+                    extra_lives_awarded: 0,
 
                     life_red_bar: 3,
 
                     energy_blue_bar: 3,
 
                     score: 0,
+                    buffs: Vec::new(),
 
                 });
 
             }
     }
 
+    // 战役中途的关卡沿用上一关结束时的玩家属性
+    for (player_type, carried_stats) in &campaign_run.players {
+        let Some(player_stats) = player_info.players.get_mut(player_type) else {
+            continue;
+        };
+        if carried_stats.life_red_bar == 0 {
+            // 上一关被摧毁的玩家重新出发，只保留分数和剩余生命
+            player_stats.score = carried_stats.score;
+            player_stats.lives = carried_stats.lives;
+            player_stats.extra_lives_awarded = carried_stats.extra_lives_awarded;
+        } else {
+            *player_stats = carried_stats.clone();
+        }
+    }
+
     // 加载字体
     let font: Handle<Font> = asset_server.load(crate::FONT_EN);
 
@@ -1840,16 +1946,44 @@ fn spawn_game_entities_if_needed(
                 spawn_ui_element_from_config(&mut commands, &font, &asset_server, &mut texture_atlas_layouts, config, &player_info, TankType::Player1);
             }
         }
-        GameMode::TwoPlayers => {
-            // 双人模式：生成玩家1和玩家2的UI
+        GameMode::TwoPlayers | GameMode::Versus => {
+            // 双人模式和对战模式：生成玩家1和玩家2的UI
             spawn_player_info(&mut commands, &font, &asset_server, &mut texture_atlas_layouts, &player_info);
         }
     }
     
-    spawn_top_text_info(&mut commands, &font, stage_level.0);
+    if *game_mode == GameMode::Versus {
+        versus::spawn_versus_hud(&mut commands, &font, &versus_match, match_options.versus_rounds);
+    } else {
+        spawn_top_text_info(&mut commands, &font, stage_level.0, match_options.active_rule(*game_mode));
+    }
+
+    // 重置道具调度器（道具随后按时间生成）
+    powerup::reset_powerup_director(&mut powerup_director, stage_level.0);
+    fortification.timer = None;
+    enemy_freeze.timer = None;
 
-    // 生成道具
-    spawn_power_ups(&mut commands, &asset_server, &mut texture_atlas_layouts, &stage_level);
+    // 生存模式从第一波开始；对战模式不出现道具，关闭中立敌人时也不生成敌方坦克
+    *enemy_spawn_state = EnemySpawnState::default();
+    if match_options.active_rule(*game_mode) == MatchRule::Survival {
+        survival::start_wave(&mut survival_state, &mut enemy_spawn_state, 1);
+    }
+
+    // 夺旗模式在敌方基地插上旗帜
+    if match_options.active_rule(*game_mode) == MatchRule::CaptureFlag {
+        ctf::spawn_flag(&mut commands, &mut capture_flag_state, stage_level.0);
+    }
+
+    // 占山为王模式按关卡文件生成占领区
+    if match_options.active_rule(*game_mode) == MatchRule::KingOfTheHill {
+        kingofthehill::spawn_control_zones(&mut commands, stage_level.0);
+    }
+    if *game_mode == GameMode::Versus {
+        powerup_director.candidate_cells.clear();
+        if !match_options.versus_enemies {
+            enemy_spawn_state.max_count = 0;
+        }
+    }
 }
 
 fn handle_start_screen_input(
@@ -1868,12 +2002,12 @@ fn handle_start_screen_input(
         menu_selection.selected_index = if menu_selection.selected_index > 0 {
             menu_selection.selected_index - 1
         } else {
-            4
+            6
         };
     }
     // S 键向下选择
     if keyboard_input.just_pressed(KeyCode::KeyS) {
-        menu_selection.selected_index = (menu_selection.selected_index + 1) % 5;
+        menu_selection.selected_index = (menu_selection.selected_index + 1) % 7;
     }
     // Space 键确认选择
     if keyboard_input.just_pressed(KeyCode::Space) {
@@ -1887,17 +2021,67 @@ fn handle_start_screen_input(
                 next_state.set(GameState::FadingOut); // 2 Player
             }
             2 => {
-                next_state.set(GameState::About); // About
+                *game_mode = GameMode::Versus;
+                next_state.set(GameState::FadingOut); // Versus
             }
             3 => {
+                next_state.set(GameState::About); // About
+            }
+            4 => {
                 next_state.set(GameState::Credits); // Credits
             }
-            4 => std::process::exit(0), // EXIT
+            5 => {
+                next_state.set(GameState::HighScores); // High Scores
+            }
+            6 => std::process::exit(0), // EXIT
             _ => {}
         }
     }
 }
 
+fn handle_match_options_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut match_options: ResMut<MatchOptions>,
+    mut text_query: Query<&mut Text2d, With<MatchOptionsText>>,
+) {
+    // M 键切换单人和双人模式的对局规则
+    if keyboard_input.just_pressed(KeyCode::KeyM) {
+        match_options.rule = match_options.rule.next();
+    }
+    // F 键切换玩家友军误伤
+    if keyboard_input.just_pressed(KeyCode::KeyF) {
+        match_options.friendly_fire = !match_options.friendly_fire;
+    }
+    // G 键切换敌方友军误伤
+    if keyboard_input.just_pressed(KeyCode::KeyG) {
+        match_options.enemy_friendly_fire = !match_options.enemy_friendly_fire;
+    }
+    // R 键切换对战局数（1 / 3 / 5 局）
+    if keyboard_input.just_pressed(KeyCode::KeyR) {
+        match_options.versus_rounds = match match_options.versus_rounds {
+            1 => 3,
+            3 => 5,
+            _ => 1,
+        };
+    }
+    // N 键切换对战模式的中立敌人
+    if keyboard_input.just_pressed(KeyCode::KeyN) {
+        match_options.versus_enemies = !match_options.versus_enemies;
+    }
+
+    let on_off = |enabled: bool| if enabled { "On" } else { "Off" };
+    for mut text in &mut text_query {
+        text.0 = format!(
+            "M: Rule {} | F: Friendly Fire {} | G: Enemy Friendly Fire {} | R: Versus Best of {} | N: Versus Enemies {}",
+            match_options.rule.label(),
+            on_off(match_options.friendly_fire),
+            on_off(match_options.enemy_friendly_fire),
+            match_options.versus_rounds,
+            on_off(match_options.versus_enemies)
+        );
+    }
+}
+
 fn animate_start_screen(
     time: Res<Time>,
     mut query: Query<(&AnimationIndices, &mut AnimationTimer, &mut Sprite, &mut CurrentAnimationFrame), With<StartScreenUI>>,
@@ -1980,7 +2164,7 @@ fn get_blocked_direction(collision_normal: Vec2) -> Vec2 {
     }
 }
 
-fn choose_available_direction(blocked_direction: Vec2) -> Vec2 {
+fn choose_available_direction(blocked_direction: Vec2, rng: &mut GameRng) -> Vec2 {
     let available_directions: Vec<Vec2> = DIRECTIONS
         .iter()
         .filter(|dir| **dir != blocked_direction)
@@ -1990,7 +2174,6 @@ fn choose_available_direction(blocked_direction: Vec2) -> Vec2 {
     if available_directions.is_empty() {
         blocked_direction
     } else {
-        let mut rng = rand::rng();
         let random_index = rng.random_range(0..available_directions.len());
         available_directions[random_index]
     }
@@ -2002,10 +2185,11 @@ fn handle_enemy_tank_collision(
     direction_timer: &mut DirectionChangeTimer,
     collision_cooldown: &mut CollisionCooldownTimer,
     rapier_context: &RapierContext,
+    rng: &mut GameRng,
 ) {
     if let Some(collision_normal) = detect_enemy_tank_collision(entity, rapier_context) {
         let blocked_direction = get_blocked_direction(collision_normal);
-        enemy_tank.direction = choose_available_direction(blocked_direction);
+        enemy_tank.direction = choose_available_direction(blocked_direction, rng);
         direction_timer.reset();
         collision_cooldown.reset();
     }
@@ -2014,8 +2198,8 @@ fn handle_enemy_tank_collision(
 fn handle_random_direction_change(
     enemy_tank: &mut EnemyTank,
     direction_timer: &mut DirectionChangeTimer,
+    rng: &mut GameRng,
 ) {
-    let mut rng = rand::rng();
     if rng.random::<f32>() < 0.4 {
         let random_index = rng.random_range(0..DIRECTIONS.len());
         enemy_tank.direction = DIRECTIONS[random_index];
@@ -2025,6 +2209,7 @@ fn handle_random_direction_change(
 
 fn update_enemy_tank_movement(
     enemy_tank: EnemyTank,
+    speed: f32,
     velocity: &mut Velocity,
     target_rotation: &mut TargetRotation,
     rotation_timer: &mut RotationTimer,
@@ -2044,7 +2229,7 @@ fn update_enemy_tank_movement(
             rotation_timer.reset();
         } else {
             // 不需要转向，正常移动
-            velocity.linvel = enemy_tank.direction * TANK_SPEED;
+            velocity.linvel = enemy_tank.direction * speed;
         }
     }
 }
@@ -2061,11 +2246,20 @@ fn move_enemy_tanks(
         &mut Transform,
         &mut RotationTimer,
         &mut TargetRotation,
+        &EnemyClass,
+        Has<Frozen>,
     )>,
     rapier_context: ReadRapierContext,
+    mut game_rng: ResMut<GameRng>,
 ) {    let rapier_context = rapier_context.single().unwrap();
 
-    for (entity, mut velocity, mut enemy_tank, mut direction_timer, mut collision_cooldown, mut transform, mut rotation_timer, mut target_rotation) in &mut query {
+    for (entity, mut velocity, mut enemy_tank, mut direction_timer, mut collision_cooldown, mut transform, mut rotation_timer, mut target_rotation, class, is_frozen) in &mut query {
+        // 被冻结的坦克原地不动，也不转向
+        if is_frozen {
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
+
         // 更新碰撞冷却计时器
         collision_cooldown.tick(time.delta());
 
@@ -2077,6 +2271,7 @@ fn move_enemy_tanks(
                 &mut direction_timer,
                 &mut collision_cooldown,
                 &rapier_context,
+                &mut game_rng,
             );
         }
 
@@ -2085,11 +2280,11 @@ fn move_enemy_tanks(
 
         // 如果计时器结束，有10%几率随机转向
         if direction_timer.just_finished() {
-            handle_random_direction_change(&mut enemy_tank, &mut direction_timer);
+            handle_random_direction_change(&mut enemy_tank, &mut direction_timer, &mut game_rng);
         }
 
         // 更新坦克移动
-        update_enemy_tank_movement(*enemy_tank, &mut velocity, &mut target_rotation, &mut rotation_timer);
+        update_enemy_tank_movement(*enemy_tank, class.speed(), &mut velocity, &mut target_rotation, &mut rotation_timer);
 
         // 更新旋转计时器
         rotation_timer.tick(time.delta());
@@ -2221,6 +2416,12 @@ fn handle_powerup_collision(
     mut player_info: ResMut<PlayerInfo>,
     mut commander_life: ResMut<CommanderLife>,
     mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+    enemy_tanks: Query<(Entity, &Transform), With<EnemyTank>>,
+    commander_walls: Query<Entity, With<CommanderWall>>,
+    mut damage_events: MessageWriter<crate::combat::Damage>,
+    mut fortification: ResMut<FortificationTimer>,
+    mut enemy_freeze: ResMut<EnemyFreezeTimer>,
+    mut stage_stats: ResMut<StageStats>,
 ) {
     for (tank_transform, player_tank, tank_entity) in player_tanks{
         let mut picked_powerup: Option<PowerUp> = None;
@@ -2242,40 +2443,11 @@ fn handle_powerup_collision(
             let powerup_sound: Handle<AudioSource> = asset_server.load(SOUND_POWERUP);
             commands.spawn(AudioPlayer::new(powerup_sound));
             let _ = commands.entity(powerup_entity).try_despawn();
+            stage_stats.players.entry(player_tank.tank_type).or_default().powerups_collected += 1;
 
             // 根据道具类型应用效果并发送事件
             if let Some(player_stats) = player_info.players.get_mut(&player_tank.tank_type) {
                 let stat_type = match powerup_type {
-                    PowerUp::SpeedUp => {
-                        if player_stats.speed < 100 {
-                            player_stats.speed += 20;
-                        }
-                        Some(StatType::Speed)
-                    }
-                    PowerUp::Protection => {
-                        if player_stats.protection < 100 {
-                            player_stats.protection += 20;
-                        }
-                        Some(StatType::Protection)
-                    }
-                    PowerUp::FireSpeed => {
-                        if player_stats.fire_speed < 100 {
-                            player_stats.fire_speed += 20;
-                        }
-                        Some(StatType::FireSpeed)
-                    }
-                    PowerUp::FireShell => {
-                        player_stats.fire_shell = true;
-                        Some(StatType::FireShell)
-                    }
-                    PowerUp::TrackChain => {
-                        player_stats.track_chain = true;
-                        Some(StatType::TrackChain)
-                    }
-                    PowerUp::Penetrate => {
-                        player_stats.penetrate = true;
-                        Some(StatType::Penetrate)
-                    }
                     PowerUp::Repair => {
                         if player_stats.life_red_bar < 3 {
                             player_stats.life_red_bar += 1;
@@ -2288,23 +2460,53 @@ fn handle_powerup_collision(
                         }
                         None // 汉堡道具不影响玩家属性，不发送事件
                     }
-                    PowerUp::AirCushion => {
-                        player_stats.air_cushion = true;
-                        // 更新 filter_groups，排除海（GROUP_2）
-                        // 玩家坦克不设置 memberships（默认所有组），filters 设置为不包含 GROUP_2
-                        if let Ok(mut controller) = controllers.get_mut(tank_entity) {
-                            controller.filter_groups = Some(CollisionGroups::new(Group::all(), Group::all() & !SEA_GROUP));
+                    PowerUp::Clock => {
+                        // 冻结场上所有敌方坦克（重复拾取刷新冻结时间），冻结结束前出现的敌方坦克也会被冻结
+                        enemy_freeze.timer = Some(Timer::from_seconds(ENEMY_FREEZE_DURATION, TimerMode::Once));
+                        for (enemy_entity, _) in enemy_tanks.iter() {
+                            commands.entity(enemy_entity).insert(Frozen(
+                                Timer::from_seconds(ENEMY_FREEZE_DURATION, TimerMode::Once)
+                            ));
                         }
-                        // 添加气泡特效标记
-                        commands.entity(tank_entity).insert(crate::constants::BubbleEffect);
-                        Some(StatType::AirCushion)
+                        None // 时钟道具不影响玩家属性，不发送事件
+                    }
+                    PowerUp::Shovel => {
+                        let tank_positions: Vec<Vec2> = player_tanks
+                            .iter()
+                            .map(|(transform, _, _)| transform)
+                            .chain(enemy_tanks.iter().map(|(_, transform)| transform))
+                            .map(|transform| transform.translation.truncate())
+                            .collect();
+                        powerup::fortify_commander_walls(&mut commands, &asset_server, &commander_walls, &tank_positions, &mut fortification);
+                        None // 铁锹道具不影响玩家属性，不发送事件
                     }
-                    PowerUp::Shell => {
-                        // 增加 1 颗子弹，最多 2 颗
-                        if player_stats.shells < 2 {
-                            player_stats.shells += 1;
+                    PowerUp::Grenade => {
+                        // 炸毁场上所有敌方坦克，分数记给拾取的玩家
+                        for (enemy_entity, _) in enemy_tanks.iter() {
+                            damage_events.write(crate::combat::Damage {
+                                target: enemy_entity,
+                                amount: 0,
+                                kind: crate::combat::DamageKind::Grenade,
+                                source: Some(player_tank.tank_type),
+                            });
                         }
-                        Some(StatType::Shell)
+                        None // 分数变更事件由击毁结算发送
+                    }
+                    PowerUp::Missile => {
+                        // 补充追踪导弹，不超过上限
+                        player_stats.missiles = (player_stats.missiles + MISSILE_AMMO_PER_PICKUP).min(MISSILE_MAX_AMMO);
+                        Some(StatType::Missile)
+                    }
+                    _ => {
+                        // 其余道具都是限时增益
+                        let stat_type = buff::powerup_buff_stat(powerup_type);
+                        if let Some(stat_type) = stat_type {
+                            buff::apply_buff(player_stats, stat_type);
+                            if stat_type == StatType::AirCushion {
+                                buff::set_air_cushion(&mut commands, controllers.get_mut(tank_entity).ok(), tank_entity, true);
+                            }
+                        }
+                        stat_type
                     }
                 };
 
@@ -2331,6 +2533,9 @@ const fn get_stat_prefix(stat_type: StatType) -> &'static str {
         StatType::Penetrate => "Penetrate:",
         StatType::AirCushion => "Air Cushion:",
         StatType::Shell => "Shells:",
+        StatType::Ricochet => "Ricochet:",
+        StatType::Missile => "Missiles:",
+        StatType::Lives => "Lives:",
         StatType::Score => "Scores",
     }
 }
@@ -2409,6 +2614,12 @@ fn is_stat_at_max_value(text: &str, player_stats: &PlayerStats) -> bool {
         player_stats.track_chain
     } else if text.starts_with("Penetrate:") {
         player_stats.penetrate
+    } else if text.starts_with("Missiles:") {
+        player_stats.missiles >= MISSILE_MAX_AMMO
+    } else if text.starts_with("Ricochet:") {
+        player_stats.ricochet
+    } else if text.starts_with("Lives:") {
+        player_stats.lives >= MAX_LIVES
     } else {
         false  // 分数等其他属性没有最大值
     }
@@ -2480,9 +2691,9 @@ fn animate_player_tank_texture(
 
 fn animate_enemy_tank_texture(
     time: Res<Time>,
-    mut query: Query<(&mut AnimationTimer, &mut Sprite, &AnimationIndices), With<EnemyTank>>,
+    mut query: Query<(&mut AnimationTimer, &mut Sprite, &AnimationIndices), (With<EnemyTank>, Without<Frozen>)>,
 ) {
-    // 敌方坦克：统一刷新
+    // 敌方坦克：统一刷新（冻结的坦克履带不动）
     for (mut timer, mut sprite, indices) in &mut query {
         timer.tick(time.delta());
         if timer.just_finished()
@@ -2496,14 +2707,40 @@ fn animate_enemy_tank_texture(
     }
 }
 
+// 冻结计时：冻结期间敌方坦克显示冰蓝色，到期后解冻
+fn update_frozen_enemies(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut enemy_freeze: ResMut<EnemyFreezeTimer>,
+    mut query: Query<(Entity, &mut Frozen, &mut Sprite, &EnemyClass), With<EnemyTank>>,
+) {
+    if let Some(timer) = &mut enemy_freeze.timer {
+        timer.tick(time.delta());
+        if timer.is_finished() {
+            enemy_freeze.timer = None;
+        }
+    }
+
+    for (entity, mut frozen, mut sprite, class) in &mut query {
+        frozen.tick(time.delta());
+        if frozen.is_finished() {
+            commands.entity(entity).remove::<Frozen>();
+            sprite.color = class.tint();
+        } else {
+            sprite.color = Color::srgb(0.5, 0.8, 1.0);
+        }
+    }
+}
+
 fn animate_enemy_born_animation(
     time: Res<Time>,
     mut commands: Commands,
-    mut query: Query<(Entity, &mut AnimationTimer, &mut Sprite, &AnimationIndices, &mut CurrentAnimationFrame, &BornPosition), With<EnemyBornAnimation>>,
+    mut query: Query<(Entity, &mut AnimationTimer, &mut Sprite, &AnimationIndices, &mut CurrentAnimationFrame, &BornPosition, &EnemyClass), With<EnemyBornAnimation>>,
     asset_server: Res<AssetServer>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    enemy_freeze: Res<EnemyFreezeTimer>,
 ) {
-    for (entity, mut timer, mut sprite, indices, mut current_frame, born_position) in &mut query {
+    for (entity, mut timer, mut sprite, indices, mut current_frame, born_position, class) in &mut query {
         timer.tick(time.delta());
 
         if timer.just_finished()
@@ -2531,12 +2768,15 @@ fn animate_enemy_born_animation(
                         let enemy_animation_indices = AnimationIndices { first: 0, last: 1 };
 
                         // 生成敌方坦克
-                        let _enemy_entity = commands.spawn_empty()
+                        let enemy_entity = commands.spawn_empty()
                             .insert(EnemyTank {
                                 direction: Vec2::new(0.0, -1.0),
                             })
                             .insert(PlayingEntity)
                             .insert(TankFireConfig::default())
+                            .insert(*class)
+                            .insert(Health::new(class.health()))
+                            .insert(Armor::default())
                             .insert(DirectionChangeTimer(Timer::from_seconds(2.0, TimerMode::Once)))
                             .insert(CollisionCooldownTimer(Timer::from_seconds(0.5, TimerMode::Once)))
                             .insert(RotationTimer(Timer::from_seconds(0.6, TimerMode::Once)))
@@ -2548,13 +2788,14 @@ fn animate_enemy_born_animation(
                                     layout: enemy_texture_atlas_layout,
                                     index: enemy_animation_indices.first,
                                 }),
+                                color: class.tint(),
                                 custom_size: Some(Vec2::new(80.0, 90.0)),
                                 ..default()
                             })
                             .insert(Transform::from_translation(born_position.0))
                             .insert(enemy_animation_indices)
                             .insert(Velocity {
-                                linvel: Vec2::new(0.0, -TANK_SPEED),
+                                linvel: Vec2::new(0.0, -class.speed()),
                                 angvel: 0.0,
                             })
                             .insert(RigidBody::Dynamic)
@@ -2566,6 +2807,13 @@ fn animate_enemy_born_animation(
                             .insert(Friction::new(0.0))
                             .insert(Restitution::new(0.0))
                             .id();
+
+                        // 时钟道具的冻结还没结束时，新出现的坦克冻结到同一时刻
+                        if let Some(freeze_timer) = &enemy_freeze.timer {
+                            commands.entity(enemy_entity).insert(Frozen(
+                                Timer::new(freeze_timer.remaining(), TimerMode::Once)
+                            ));
+                        }
                     }
                 }
             }
@@ -2624,17 +2872,17 @@ fn animate_player_avatar(
 fn animate_commander(
     time: Res<Time>,
     asset_server: Res<AssetServer>,
-    commander_life: Res<CommanderLife>,
     mut query: Query<(
         &mut AnimationTimer,
         &mut Sprite,
         &AnimationIndices,
         &mut CurrentAnimationFrame,
+        &Health,
     ), With<Commander>>,
 ) {
-    for (mut timer, mut sprite, indices, mut current_frame) in &mut query {
+    for (mut timer, mut sprite, indices, mut current_frame, health) in &mut query {
         // 如果Commander已死亡，切换到死亡图片并停止动画
-        if commander_life.life_red_bar == 0 {
+        if health.current == 0 {
             let dead_texture: Handle<Image> = asset_server.load(TEXTURE_COMMANDER_DEAD);
             sprite.image = dead_texture;
             sprite.texture_atlas = None;
@@ -2804,162 +3052,6 @@ fn handle_recoil_force(
     }
 }
 
-/// 激光碰撞检测系统（只收集实体，不立即销毁）
-fn laser_collision_system(
-    mut commands: Commands,
-    mut frame_count: Local<u32>,
-    lasers: Query<(Entity, &Transform, &CurrentAnimationFrame, &AnimationIndices), With<Laser>>,
-    enemies: Query<(Entity, &Transform), With<EnemyTank>>,
-    bullets: Query<(Entity, &Transform), With<BulletOwner>>,
-    bricks: Query<(Entity, &Transform), With<Brick>>,
-    steels: Query<(Entity, &Transform), With<Steel>>,
-    forests: Query<(Entity, &Transform), With<Forest>>,
-    barriers: Query<(Entity, &Transform), With<Barrier>>,
-    seas: Query<(Entity, &Transform), With<Sea>>,
-) {
-    // 每5帧执行一次碰撞检测
-    *frame_count += 1;
-    if *frame_count % 5 != 0 {
-        return;
-    }
-    
-    for (_laser_entity, laser_transform, _, _) in &lasers {
-        // 激光原始尺寸（未旋转）
-        let laser_half_width = 35.0; // 70 / 2
-        let laser_half_height = 683.0; // 1366 / 2 (1倍)
-        
-        // 获取激光的旋转角度
-        let rotation = laser_transform.rotation;
-        
-        // 激光的四个角点（未旋转）
-        let corners = [
-            Vec2::new(-laser_half_width, -laser_half_height),
-            Vec2::new(laser_half_width, -laser_half_height),
-            Vec2::new(laser_half_width, laser_half_height),
-            Vec2::new(-laser_half_width, laser_half_height),
-        ];
-        
-        // 旋转每个角点并加上位置
-        let rotated_corners: Vec<Vec2> = corners.iter()
-            .map(|corner| {
-                let rotated = rotation.mul_vec3(corner.extend(0.0));
-                Vec2::new(rotated.x, rotated.y) + Vec2::new(laser_transform.translation.x, laser_transform.translation.y)
-            })
-            .collect();
-        
-        // 计算旋转后的边界框
-        let laser_left = rotated_corners.iter().map(|p| p.x).fold(f32::INFINITY, f32::min);
-        let laser_right = rotated_corners.iter().map(|p| p.x).fold(f32::NEG_INFINITY, f32::max);
-        let laser_bottom = rotated_corners.iter().map(|p| p.y).fold(f32::INFINITY, f32::min);
-        let laser_top = rotated_corners.iter().map(|p| p.y).fold(f32::NEG_INFINITY, f32::max);
-
-        // 检测与敌方坦克的碰撞
-        for (enemy_entity, enemy_transform) in &enemies {
-            let enemy_left = enemy_transform.translation.x - TANK_WIDTH / 2.0;
-            let enemy_right = enemy_transform.translation.x + TANK_WIDTH / 2.0;
-            let enemy_top = enemy_transform.translation.y + TANK_HEIGHT / 2.0;
-            let enemy_bottom = enemy_transform.translation.y - TANK_HEIGHT / 2.0;
-
-            // 简单的AABB碰撞检测
-            if laser_left < enemy_right && laser_right > enemy_left &&
-               laser_bottom < enemy_top && laser_top > enemy_bottom {
-                // 标记敌方坦克为待销毁
-                let _ = commands.entity(enemy_entity).try_insert(DespawnMarker);
-            }
-        }
-
-        // 检测与子弹的碰撞
-        for (bullet_entity, bullet_transform) in &bullets {
-            let bullet_left = bullet_transform.translation.x - BULLET_SIZE / 2.0;
-            let bullet_right = bullet_transform.translation.x + BULLET_SIZE / 2.0;
-            let bullet_top = bullet_transform.translation.y + BULLET_SIZE / 2.0;
-            let bullet_bottom = bullet_transform.translation.y - BULLET_SIZE / 2.0;
-
-            // 简单的AABB碰撞检测
-            if laser_left < bullet_right && laser_right > bullet_left &&
-               laser_bottom < bullet_top && laser_top > bullet_bottom {
-                // 标记子弹为待销毁
-                let _ = commands.entity(bullet_entity).try_insert(DespawnMarker);
-            }
-        }
-
-        // 检测与砖块的碰撞
-        for (brick_entity, brick_transform) in &bricks {
-            let brick_left = brick_transform.translation.x - BRICK_WIDTH / 2.0;
-            let brick_right = brick_transform.translation.x + BRICK_WIDTH / 2.0;
-            let brick_top = brick_transform.translation.y + BRICK_HEIGHT / 2.0;
-            let brick_bottom = brick_transform.translation.y - BRICK_HEIGHT / 2.0;
-
-            // 简单的AABB碰撞检测
-            if laser_left < brick_right && laser_right > brick_left &&
-               laser_bottom < brick_top && laser_top > brick_bottom {
-                // 标记砖块为待销毁
-                let _ = commands.entity(brick_entity).try_insert(DespawnMarker);
-            }
-        }
-
-        // 检测与钢块的碰撞
-        for (steel_entity, steel_transform) in &steels {
-            let steel_left = steel_transform.translation.x - BRICK_WIDTH / 2.0;
-            let steel_right = steel_transform.translation.x + BRICK_WIDTH / 2.0;
-            let steel_top = steel_transform.translation.y + BRICK_HEIGHT / 2.0;
-            let steel_bottom = steel_transform.translation.y - BRICK_HEIGHT / 2.0;
-
-            // 简单的AABB碰撞检测
-            if laser_left < steel_right && laser_right > steel_left &&
-               laser_bottom < steel_top && laser_top > steel_bottom {
-                // 标记钢块为待销毁
-                let _ = commands.entity(steel_entity).try_insert(DespawnMarker);
-            }
-        }
-
-        // 检测与森林的碰撞
-        for (forest_entity, forest_transform) in &forests {
-            let forest_left = forest_transform.translation.x - BRICK_WIDTH / 2.0;
-            let forest_right = forest_transform.translation.x + BRICK_WIDTH / 2.0;
-            let forest_top = forest_transform.translation.y + BRICK_HEIGHT / 2.0;
-            let forest_bottom = forest_transform.translation.y - BRICK_HEIGHT / 2.0;
-
-            // 简单的AABB碰撞检测
-            if laser_left < forest_right && laser_right > forest_left &&
-               laser_bottom < forest_top && laser_top > forest_bottom {
-                // 标记森林为待销毁
-                let _ = commands.entity(forest_entity).try_insert(DespawnMarker);
-            }
-        }
-
-        // 检测与障碍的碰撞
-        for (barrier_entity, barrier_transform) in &barriers {
-            let barrier_left = barrier_transform.translation.x - BRICK_WIDTH / 2.0;
-            let barrier_right = barrier_transform.translation.x + BRICK_WIDTH / 2.0;
-            let barrier_top = barrier_transform.translation.y + BRICK_HEIGHT / 2.0;
-            let barrier_bottom = barrier_transform.translation.y - BRICK_HEIGHT / 2.0;
-
-            // 简单的AABB碰撞检测
-            if laser_left < barrier_right && laser_right > barrier_left &&
-               laser_bottom < barrier_top && laser_top > barrier_bottom {
-                // 标记障碍为待销毁
-                let _ = commands.entity(barrier_entity).try_insert(DespawnMarker);
-            }
-        }
-
-        // 检测与sea的碰撞
-        for (sea_entity, sea_transform) in &seas {
-            let sea_left = sea_transform.translation.x - BRICK_WIDTH / 2.0;
-            let sea_right = sea_transform.translation.x + BRICK_WIDTH / 2.0;
-            let sea_top = sea_transform.translation.y + BRICK_HEIGHT / 2.0;
-            let sea_bottom = sea_transform.translation.y - BRICK_HEIGHT / 2.0;
-
-            // 简单的AABB碰撞检测
-            if laser_left < sea_right && laser_right > sea_left &&
-               laser_bottom < sea_top && laser_top > sea_bottom {
-                // 标记sea为待销毁
-                let _ = commands.entity(sea_entity).try_insert(DespawnMarker);
-            }
-        }
-    }
-}
-
 fn animate_forest_fire(
     time: Res<Time>,
     mut commands: Commands,
@@ -3076,17 +3168,13 @@ fn play_commander_music(
     commander: Query<&Transform, With<Commander>>,
     ambience_players: Query<Entity, With<CommanderAmbiencePlayer>>,
 ) {
-    // 获取 Commander 位置
-    let commander_transform = match commander.single() {
-        Ok(t) => t,
-        Err(_) => return,
-    };
-
-    // 计算最近的玩家坦克距离
+    // 计算最近的玩家坦克与 Commander 的距离（对战模式有两个 Commander）
     let mut min_distance = f32::MAX;
-    for player_transform in player_tanks.iter() {
-        let distance = player_transform.translation.distance(commander_transform.translation);
-        min_distance = min_distance.min(distance);
+    for commander_transform in commander.iter() {
+        for player_transform in player_tanks.iter() {
+            let distance = player_transform.translation.distance(commander_transform.translation);
+            min_distance = min_distance.min(distance);
+        }
     }
 
     // 根据距离判断是否播放音乐
@@ -3130,7 +3218,8 @@ fn handle_game_over_delay(
         timer.tick(time.delta());
         if timer.is_finished() {
             let _ = commands.entity(entity).try_despawn();
-            next_state.set(GameState::GameOver);
+            // 先让上榜的玩家输入名字，再进入结束界面
+            next_state.set(GameState::NameEntry);
         }
     }
 }
@@ -3140,10 +3229,12 @@ fn check_game_over(
     player_info: Res<PlayerInfo>,
     game_mode: Res<GameMode>,
     commander_life: Res<CommanderLife>,
+    match_options: Res<MatchOptions>,
     existing_timers: Query<(), With<GameOverTimer>>,
 ) {
     // 如果已经存在 GameOverTimer，说明已经触发了 GameOver，不再重复触发
-    if !existing_timers.is_empty() {
+    // （对战模式没有 Game Over，回合胜负由 versus 模块判定）
+    if !existing_timers.is_empty() || *game_mode == GameMode::Versus {
         return;
     }
 
@@ -3157,17 +3248,18 @@ fn check_game_over(
         return;
     }
 
-    // 检测所有玩家生命值是否都为 0
-    let all_players_dead = if player_info.players.is_empty() {
+    // 检测所有玩家是否都已被摧毁且没有剩余生命（生存模式可以无限复活，只有司令官阵亡时结束）
+    let is_out = |p: &PlayerStats| p.life_red_bar == 0 && p.lives == 0;
+    let all_players_dead = if player_info.players.is_empty() || match_options.unlimited_lives(*game_mode) {
         false
     } else {
         match *game_mode {
             GameMode::OnePlayer => {
-                player_info.players.get(&TankType::Player1).map_or(false, |p| p.life_red_bar == 0)
+                player_info.players.get(&TankType::Player1).is_some_and(is_out)
             }
-            GameMode::TwoPlayers => {
-                player_info.players.get(&TankType::Player1).map_or(false, |p| p.life_red_bar == 0)
-                    && player_info.players.get(&TankType::Player2).map_or(false, |p| p.life_red_bar == 0)
+            GameMode::TwoPlayers | GameMode::Versus => {
+                player_info.players.get(&TankType::Player1).is_some_and(is_out)
+                    && player_info.players.get(&TankType::Player2).is_some_and(is_out)
             }
         }
     };
@@ -3289,6 +3381,7 @@ fn handle_recall_input(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     query: Query<(Entity, &Transform, &PlayerTank), With<PlayerTank>>,
     mut recall_timers: ResMut<RecallTimers>,
+    game_mode: Res<GameMode>,
 ) {
     for (entity, transform, player_tank) in &query {
         // 检查是否正在回城
@@ -3305,11 +3398,7 @@ fn handle_recall_input(
 
         if is_recall_key_pressed && !is_recalling {
             // 计算初始位置
-            let initial_position = if player_tank.tank_type == TankType::Player1 {
-                Vec3::new(-TANK_WIDTH / 2.0 - COMMANDER_WIDTH/2.0 - 50.0, MAP_BOTTOM_Y+TANK_HEIGHT / 2.0, 0.0)
-            } else {
-                Vec3::new(TANK_WIDTH / 2.0 + COMMANDER_WIDTH/2.0 + 50.0, MAP_BOTTOM_Y+TANK_HEIGHT / 2.0, 0.0)
-            };
+            let initial_position = player_start_position(player_tank.tank_type, *game_mode);
 
             // 开始回城
             let recall_timer = RecallTimer::new(initial_position, RECALL_TIME);
@@ -3529,17 +3618,14 @@ fn handle_dash_collision(
     mut commands: Commands,
     mut collision_events: MessageReader<CollisionEvent>,
     mut effect_events: MessageWriter<crate::bullet::EffectEvent>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     asset_server: Res<AssetServer>,
     player_tanks: Query<(Entity, &PlayerTank, Option<&IsDashing>)>,
-    player_tanks_with_transform: Query<(Entity, &Transform), With<PlayerTank>>,
     enemy_tanks: Query<(Entity, &Transform), With<EnemyTank>>,
     bricks: Query<(Entity, &Transform), With<Brick>>,
     steels: Query<(Entity, &Transform), With<Steel>>,
-    mut player_info: ResMut<PlayerInfo>,
-    player_avatars: Query<(Entity, &PlayerUI), With<PlayerAvatar>>,
-    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+    player_info: Res<PlayerInfo>,
     mut dash_damage_tracker: ResMut<DashDamageTracker>,
+    mut damage_events: MessageWriter<crate::combat::Damage>,
 ) {
     for event in collision_events.read() {
         if let CollisionEvent::Started(e1, e2, _) = event {
@@ -3558,18 +3644,7 @@ fn handle_dash_collision(
                         (player_entity, None, Some(*e2), None)
                     } else {
                         // protection < 100%，玩家死亡
-                        handle_steel_collision(
-                            &mut commands,
-                            &mut effect_events,
-                            &asset_server,
-                            &mut texture_atlas_layouts,
-                            &player_tanks,
-                            &player_tanks_with_transform,
-                            &mut player_info,
-                            &player_avatars,
-                            &mut stat_changed_events,
-                            player_entity,
-                        );
+                        handle_steel_collision(&mut damage_events, player_entity);
                         continue;
                     }
                 } else if is_dashing.is_some() && bricks.get(*e2).is_ok() {
@@ -3593,18 +3668,7 @@ fn handle_dash_collision(
                         (player_entity, None, Some(*e1), None)
                     } else {
                         // protection < 100%，玩家死亡
-                        handle_steel_collision(
-                            &mut commands,
-                            &mut effect_events,
-                            &asset_server,
-                            &mut texture_atlas_layouts,
-                            &player_tanks,
-                            &player_tanks_with_transform,
-                            &mut player_info,
-                            &player_avatars,
-                            &mut stat_changed_events,
-                            player_entity,
-                        );
+                        handle_steel_collision(&mut damage_events, player_entity);
                         continue;
                     }
                 } else if is_dashing.is_some() && bricks.get(*e1).is_ok() {
@@ -3626,13 +3690,8 @@ fn handle_dash_collision(
                     &mut commands,
                     &mut effect_events,
                     &asset_server,
-                    &mut texture_atlas_layouts,
-                    &player_tanks,
-                    &player_tanks_with_transform,
                     &bricks,
-                    &mut player_info,
-                    &player_avatars,
-                    &mut stat_changed_events,
+                    &mut damage_events,
                     player_entity,
                     b_entity,
                     &mut dash_damage_tracker,
@@ -3655,16 +3714,8 @@ fn handle_dash_collision(
             // 处理敌方坦克碰撞
             if let Some(e_entity) = enemy_entity {
                 handle_dash_enemy_tank_collision(
-                    &mut commands,
-                    &mut effect_events,
-                    &asset_server,
-                    &mut texture_atlas_layouts,
+                    &mut damage_events,
                     &player_tanks,
-                    &player_tanks_with_transform,
-                    &enemy_tanks,
-                    &mut player_info,
-                    &player_avatars,
-                    &mut stat_changed_events,
                     player_entity,
                     e_entity,
                     &mut dash_damage_tracker,
@@ -3727,22 +3778,12 @@ fn handle_brick_collision(
     commands: &mut Commands,
     effect_events: &mut MessageWriter<crate::bullet::EffectEvent>,
     asset_server: &Res<AssetServer>,
-    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
-    player_tanks: &Query<(Entity, &PlayerTank, Option<&IsDashing>)>,
-    player_tanks_with_transform: &Query<(Entity, &Transform), With<PlayerTank>>,
     bricks: &Query<(Entity, &Transform), With<Brick>>,
-    player_info: &mut ResMut<PlayerInfo>,
-    player_avatars: &Query<(Entity, &PlayerUI), With<PlayerAvatar>>,
-    _stat_changed_events: &mut MessageWriter<PlayerStatChanged>,
+    damage_events: &mut MessageWriter<crate::combat::Damage>,
     player_entity: Entity,
     brick_entity: Entity,
     dash_damage_tracker: &mut DashDamageTracker,
 ) {
-    // 获取玩家坦克信息
-    let player_tank = player_tanks.iter().find_map(|(e, pt, _)| {
-        if e == player_entity { Some(pt) } else { None }
-    }).unwrap();
-
     // 获取 brick 位置用于生成效果
     if let Ok((_, brick_transform)) = bricks.get(brick_entity) {
         // 播放砖块被击中的音效
@@ -3761,113 +3802,39 @@ fn handle_brick_collision(
         let _ = commands.entity(brick_entity).try_despawn();
     }
 
+    apply_dash_ram_damage(damage_events, player_entity, dash_damage_tracker);
+}
+
+/// 冲刺撞击对玩家自己造成伤害，每次冲刺只结算一次（扣血量由护甲决定）
+fn apply_dash_ram_damage(
+    damage_events: &mut MessageWriter<crate::combat::Damage>,
+    player_entity: Entity,
+    dash_damage_tracker: &mut DashDamageTracker,
+) {
     // 检查本次 dash 是否已经扣过血
-    if dash_damage_tracker.has_taken_damage.contains(&player_entity) {
+    if !dash_damage_tracker.has_taken_damage.insert(player_entity) {
         return; // 已经扣过血，不再重复扣血
     }
 
-    // 根据 protection 百分比决定扣血量
-    if let Some(player_stats) = player_info.players.get_mut(&player_tank.tank_type) {
-        let health_cost = if player_stats.protection < 40 {
-            2 // 2/3血条
-        } else if player_stats.protection < 80 {
-            1 // 1/3血条
-        } else {
-            0 // 不扣血
-        };
-
-        player_stats.life_red_bar = player_stats.life_red_bar.saturating_sub(health_cost);
-
-        // 标记本次 dash 已经扣过血
-        if health_cost > 0 {
-            dash_damage_tracker.has_taken_damage.insert(player_entity);
-        }
-
-        // 检查玩家是否死亡
-        if player_stats.life_red_bar == 0 {
-            // 获取玩家坦克位置用于生成爆炸效果
-            if let Ok((_, tank_transform)) = player_tanks_with_transform.get(player_entity) {
-                // 生成爆炸效果
-                spawn_explosion(commands, asset_server, texture_atlas_layouts, tank_transform.translation);
-            }
-
-            // 销毁玩家坦克
-            let _ = commands.entity(player_entity).try_despawn();
-
-            // 标记对应玩家的头像为死亡状态
-            for (avatar_entity, player_index) in player_avatars.iter() {
-                if player_index.player_type == player_tank.tank_type {
-                    commands.entity(avatar_entity).insert(PlayerDead);
-                }
-            }
-
-            // 启动 Game Over 延迟计时器（1.2秒）
-            commands.spawn((
-                GameOverTimer,
-                AnimationTimer(Timer::from_seconds(1.2, TimerMode::Once)),
-            ));
-        }
-    }
+    damage_events.write(crate::combat::Damage {
+        target: player_entity,
+        amount: DASH_RAM_DAMAGE,
+        kind: crate::combat::DamageKind::Ram,
+        source: None,
+    });
 }
 
 fn handle_steel_collision(
-    commands: &mut Commands,
-    effect_events: &mut MessageWriter<crate::bullet::EffectEvent>,
-    _asset_server: &Res<AssetServer>,
-    _texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
-    player_tanks: &Query<(Entity, &PlayerTank, Option<&IsDashing>)>,
-    player_tanks_with_transform: &Query<(Entity, &Transform), With<PlayerTank>>,
-    player_info: &mut ResMut<PlayerInfo>,
-    player_avatars: &Query<(Entity, &PlayerUI), With<PlayerAvatar>>,
-    _stat_changed_events: &mut MessageWriter<PlayerStatChanged>,
+    damage_events: &mut MessageWriter<crate::combat::Damage>,
     player_entity: Entity,
 ) {
-    // 获取玩家坦克信息
-    let player_tank = player_tanks.iter().find_map(|(e, pt, _)| {
-        if e == player_entity { Some(pt) } else { None }
-    }).unwrap();
-
-    // 检查 protection 是否为 100%
-    let can_break_steel = if let Some(player_stats) = player_info.players.get(&player_tank.tank_type) {
-        player_stats.protection >= 100
-    } else {
-        false
-    };
-
-    if can_break_steel {
-        // protection = 100%，可以撞碎铁块，不扣血
-        // 发送火花特效事件
-        if let Ok((_, tank_transform)) = player_tanks_with_transform.get(player_entity) {
-            effect_events.write(crate::bullet::EffectEvent::Spark {
-                position: tank_transform.translation,
-            });
-        }
-        // 铁块被撞碎的效果（可以在这里添加更多效果）
-    } else {
-        // protection < 100%，玩家死亡
-        // 发送爆炸特效事件
-        if let Ok((_, tank_transform)) = player_tanks_with_transform.get(player_entity) {
-            effect_events.write(crate::bullet::EffectEvent::Explosion {
-                position: tank_transform.translation,
-            });
-        }
-
-        // 销毁玩家坦克
-        let _ = commands.entity(player_entity).try_despawn();
-
-        // 标记对应玩家的头像为死亡状态
-        for (avatar_entity, player_index) in player_avatars.iter() {
-            if player_index.player_type == player_tank.tank_type {
-                commands.entity(avatar_entity).insert(PlayerDead);
-            }
-        }
-
-        // 启动 Game Over 延迟计时器（1.2秒）
-        commands.spawn((
-            GameOverTimer,
-            AnimationTimer(Timer::from_seconds(1.2, TimerMode::Once)),
-        ));
-    }
+    // protection < 100%，撞上铁块的玩家坦克被摧毁
+    damage_events.write(crate::combat::Damage {
+        target: player_entity,
+        amount: 0,
+        kind: crate::combat::DamageKind::Crush,
+        source: None,
+    });
 }
 
 fn handle_steel_break(
@@ -3890,98 +3857,34 @@ fn handle_steel_break(
 }
 
 fn handle_dash_enemy_tank_collision(
-    mut commands: &mut Commands,
-    effect_events: &mut MessageWriter<crate::bullet::EffectEvent>,
-    asset_server: &Res<AssetServer>,
-    mut texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    damage_events: &mut MessageWriter<crate::combat::Damage>,
     player_tanks: &Query<(Entity, &PlayerTank, Option<&IsDashing>)>,
-    player_tanks_with_transform: &Query<(Entity, &Transform), With<PlayerTank>>,
-    enemy_tanks: &Query<(Entity, &Transform), With<EnemyTank>>,
-    player_info: &mut ResMut<PlayerInfo>,
-    player_avatars: &Query<(Entity, &PlayerUI), With<PlayerAvatar>>,
-    stat_changed_events: &mut MessageWriter<PlayerStatChanged>,
     player_entity: Entity,
     enemy_entity: Entity,
     dash_damage_tracker: &mut DashDamageTracker,
 ) {
     // 获取玩家坦克信息
-    let (_, player_tank, _) = player_tanks.get(player_entity).unwrap();
-
-    // 获取敌方坦克位置用于生成爆炸效果
-    if let Ok((_, enemy_transform)) = enemy_tanks.get(enemy_entity) {
-        // 发送爆炸特效事件
-        effect_events.write(crate::bullet::EffectEvent::Explosion {
-            position: enemy_transform.translation,
-        });
-    }
-
-    // 销毁敌方坦克
-    let _ = commands.entity(enemy_entity).try_despawn();
-
-    // 检查本次 dash 是否已经扣过血
-    if dash_damage_tracker.has_taken_damage.contains(&player_entity) {
-        return; // 已经扣过血，不再重复扣血
-    }
-
-    // 增加分数
-    if let Some(player_stats) = player_info.players.get_mut(&player_tank.tank_type) {
-        player_stats.score += 100;
-
-        // 发送分数变更事件
-        stat_changed_events.write(PlayerStatChanged {
-            player_type: player_tank.tank_type,
-            stat_type: StatType::Score,
-        });
-
-        // 根据 protection 百分比决定扣血量
-        let health_cost = if player_stats.protection < 40 {
-            2 // 2/3血条
-        } else if player_stats.protection < 80 {
-            1 // 1/3血条
-        } else {
-            0 // 不扣血
-        };
-        player_stats.life_red_bar = player_stats.life_red_bar.saturating_sub(health_cost);
-
-        // 标记本次 dash 已经扣过血
-        if health_cost > 0 {
-            dash_damage_tracker.has_taken_damage.insert(player_entity);
-        }
-
-        // 检查玩家是否死亡
-        if player_stats.life_red_bar == 0 {
-            // 获取玩家坦克位置用于生成爆炸效果
-            if let Ok((_, tank_transform)) = player_tanks_with_transform.get(player_entity) {
-                // 生成爆炸效果
-                spawn_explosion(&mut commands, &asset_server, &mut texture_atlas_layouts, tank_transform.translation);
-            }
-
-            // 销毁玩家坦克
-            let _ = commands.entity(player_entity).try_despawn();
+    let Ok((_, player_tank, _)) = player_tanks.get(player_entity) else {
+        return;
+    };
 
-            // 标记对应玩家的头像为死亡状态
-            for (avatar_entity, player_index) in player_avatars.iter() {
-                if player_index.player_type == player_tank.tank_type {
-                    commands.entity(avatar_entity).insert(PlayerDead);
-                }
-            }
+    // 撞击敌方坦克（击毁、爆炸特效和计分统一结算）
+    damage_events.write(crate::combat::Damage {
+        target: enemy_entity,
+        amount: 1,
+        kind: crate::combat::DamageKind::Ram,
+        source: Some(player_tank.tank_type),
+    });
 
-            // 启动 Game Over 延迟计时器（1.2秒）
-            commands.spawn((
-                GameOverTimer,
-                AnimationTimer(Timer::from_seconds(1.2, TimerMode::Once)),
-            ));
-        }
-    }
+    apply_dash_ram_damage(damage_events, player_entity, dash_damage_tracker);
 }
 
 fn handle_barrier_collision(
     time: Res<Time>,
-    player_tanks: Query<(Entity, &Transform, &PlayerTank), With<PlayerTank>>,
+    player_tanks: Query<(Entity, &Transform), With<PlayerTank>>,
     barriers: Query<(&Transform, Entity), With<Barrier>>,
-    mut player_info: ResMut<PlayerInfo>,
     mut barrier_damage_tracker: ResMut<BarrierDamageTracker>,
-    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+    mut damage_events: MessageWriter<crate::combat::Damage>,
 ) {
     // 更新所有冷却计时器
     for (_, timer) in barrier_damage_tracker.cooldowns.iter_mut() {
@@ -3989,7 +3892,7 @@ fn handle_barrier_collision(
     }
 
     // 检测玩家坦克与 barrier 的距离
-    for (player_entity, player_transform, player_tank) in player_tanks.iter() {
+    for (player_entity, player_transform) in player_tanks.iter() {
         for (barrier_transform, _barrier_entity) in barriers.iter() {
             // 计算距离
             let distance = (player_transform.translation - barrier_transform.translation).length();
@@ -4004,39 +3907,19 @@ fn handle_barrier_collision(
                     .map_or(true, |timer| timer.is_finished());
 
                 if can_take_damage {
-                    // 检查玩家是否有 track_chain，如果有则免疫伤害
-                    let has_track_chain = if let Some(player_stats) = player_info.players.get(&player_tank.tank_type) {
-                        player_stats.track_chain
-                    } else {
-                        false
-                    };
-
-                    if has_track_chain {
-                        // 拥有 track_chain，免疫伤害，直接跳过
-                        continue;
-                    }
-
                     // 设置 2 秒冷却
                     barrier_damage_tracker.cooldowns.insert(
                         player_entity,
                         Timer::from_seconds(2.0, TimerMode::Once)
                     );
 
-                    // 永久减少 speed 20 和 protection 20（固定值）
-                    if let Some(player_stats) = player_info.players.get_mut(&player_tank.tank_type) {
-                        player_stats.speed = player_stats.speed.saturating_sub(20);
-                        player_stats.protection = player_stats.protection.saturating_sub(20);
-
-                        // 发送 speed 和 protection 变更事件
-                        stat_changed_events.write(PlayerStatChanged {
-                            player_type: player_tank.tank_type,
-                            stat_type: StatType::Speed,
-                        });
-                        stat_changed_events.write(PlayerStatChanged {
-                            player_type: player_tank.tank_type,
-                            stat_type: StatType::Protection,
-                        });
-                    }
+                    // 永久减少 speed 和 protection（拥有 track_chain 时免疫，统一结算）
+                    damage_events.write(crate::combat::Damage {
+                        target: player_entity,
+                        amount: 1,
+                        kind: crate::combat::DamageKind::Barrier,
+                        source: None,
+                    });
                 }
             }
         }
@@ -4078,6 +3961,9 @@ fn spawn_stage_intro(
     mut stage_intro_timer: ResMut<StageIntroTimer>,
     mut clear_color: ResMut<ClearColor>,
     stage_level: Res<StageLevel>,
+    game_mode: Res<GameMode>,
+    versus_match: Res<VersusMatch>,
+    match_options: Res<MatchOptions>,
 ) {
     // 设置背景色为白色
     clear_color.0 = Color::srgb(1.0, 1.0, 1.0);
@@ -4094,7 +3980,13 @@ fn spawn_stage_intro(
     // Stage 标题（显示在屏幕中心）
     commands.spawn((
         StageIntroUI,
-        Text2d(format!("Stage {}", stage_level.0)),
+        Text2d(if *game_mode == GameMode::Versus {
+            format!("Round {}", versus_match.round)
+        } else if match_options.rule == MatchRule::Survival {
+            "Survival".to_string()
+        } else {
+            format!("Stage {}", stage_level.0)
+        }),
         TextFont {
             font_size: 80.0,
             font: font_en.clone(),
@@ -4231,56 +4123,69 @@ fn get_text_update_fn(prefix: &str) -> TextUpdateFn {
         },
         s if s.starts_with("Speed") => |stats, _| {
             Some(if stats.speed < 100 {
-                format!("Speed: {}%", stats.speed)
+                format!("Speed: {}%{}", stats.speed, stats.buff_countdown(StatType::Speed))
             } else {
-                "Speed: Max".to_string()
+                format!("Speed: Max{}", stats.buff_countdown(StatType::Speed))
             })
         },
         s if s.starts_with("Shells") => |stats, _| {
-            Some(format!("Shells: {}", stats.shells))
+            Some(format!("Shells: {}{}", stats.shells, stats.buff_countdown(StatType::Shell)))
         },
         s if s.starts_with("Protection") => |stats, _| {
             Some(if stats.protection < 100 {
-                format!("Protection: {}%", stats.protection)
+                format!("Protection: {}%{}", stats.protection, stats.buff_countdown(StatType::Protection))
             } else {
-                "Protection: Max".to_string()
+                format!("Protection: Max{}", stats.buff_countdown(StatType::Protection))
             })
         },
         s if s.starts_with("Fire Speed") => |stats, _| {
             Some(if stats.fire_speed < 100 {
-                format!("Fire Speed: {}%", stats.fire_speed)
+                format!("Fire Speed: {}%{}", stats.fire_speed, stats.buff_countdown(StatType::FireSpeed))
             } else {
-                "Fire Speed: Max".to_string()
+                format!("Fire Speed: Max{}", stats.buff_countdown(StatType::FireSpeed))
             })
         },
         s if s.starts_with("Fire Shell") => |stats, _| {
             if stats.fire_shell {
-                Some("Fire Shell: On".to_string())
+                Some(format!("Fire Shell: On{}", stats.buff_countdown(StatType::FireShell)))
             } else {
                 Some("Fire Shell: Off".to_string())
             }
         },
         s if s.starts_with("Air Cushion") => |stats, _| {
             if stats.air_cushion {
-                Some("Air Cushion: On".to_string())
+                Some(format!("Air Cushion: On{}", stats.buff_countdown(StatType::AirCushion)))
             } else {
                 Some("Air Cushion: Off".to_string())
             }
         },
         s if s.starts_with("Track Chain") => |stats, _| {
             if stats.track_chain {
-                Some("Track Chain: On".to_string())
+                Some(format!("Track Chain: On{}", stats.buff_countdown(StatType::TrackChain)))
             } else {
                 Some("Track Chain: Off".to_string())
             }
         },
         s if s.starts_with("Penetrate") => |stats, _| {
             if stats.penetrate {
-                Some("Penetrate: On".to_string())
+                Some(format!("Penetrate: On{}", stats.buff_countdown(StatType::Penetrate)))
             } else {
                 Some("Penetrate: Off".to_string())
             }
         },
+        s if s.starts_with("Missiles") => |stats, _| {
+            Some(format!("Missiles: {}", stats.missiles))
+        },
+        s if s.starts_with("Lives") => |stats, _| {
+            Some(format!("Lives: {}", stats.lives))
+        },
+        s if s.starts_with("Ricochet") => |stats, _| {
+            if stats.ricochet {
+                Some(format!("Ricochet: On{}", stats.buff_countdown(StatType::Ricochet)))
+            } else {
+                Some("Ricochet: Off".to_string())
+            }
+        },
         _ => |_, _| None,
     }
 }
@@ -4648,14 +4553,29 @@ fn cleanup_playing_entities(
     mut stage_level: ResMut<StageLevel>,
     mut commander_life: ResMut<CommanderLife>,
     mut entities_spawned: ResMut<GameEntitiesSpawned>,
+    mut campaign_run: ResMut<CampaignRun>,
+    mut stage_stats: ResMut<StageStats>,
+    mut respawn_timers: ResMut<PlayerRespawnTimers>,
+    mut versus_match: ResMut<VersusMatch>,
+    mut survival_state: ResMut<SurvivalState>,
+    mut hill_state: ResMut<KingOfTheHillState>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     // 清理所有游戏实体
     for entity in playing_entities.iter() {
         commands.entity(entity).try_despawn();
     }
 
-    // 重置玩家信息
+    // 重置玩家信息、战役进度、本关统计、复活计时、对战比分、生存波次和控制分（开始新游戏或重新开始）
     player_info.players.clear();
+    *versus_match = VersusMatch::default();
+    *survival_state = SurvivalState::default();
+    *hill_state = KingOfTheHillState::default();
+    campaign_run.players.clear();
+    *stage_stats = StageStats::default();
+    respawn_timers.timers.clear();
+    campaign_run.seed = rand::rng().random();
+    *game_rng = GameRng::from_seed(campaign_run.seed);
 
     // 重置敌方坦克计数
     enemy_spawn_state.has_spawned = 0;
@@ -4674,15 +4594,44 @@ fn cleanup_playing_entities(
 fn check_stage_complete(
     enemy_spawn_state: Res<EnemySpawnState>,
     enemies: Query<(), With<EnemyTank>>,
+    born_animations: Query<(), With<EnemyBornAnimation>>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut stage_level: ResMut<StageLevel>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut campaign_run: ResMut<CampaignRun>,
+    mut stage_stats: ResMut<StageStats>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    capture_flag_state: Res<CaptureFlagState>,
+    hill_state: Res<KingOfTheHillState>,
 ) {
+    // 对战模式消灭敌方坦克不会结束回合，生存模式的波次由 survival 模块处理
+    if *game_mode == GameMode::Versus || match_options.active_rule(*game_mode) == MatchRule::Survival {
+        return;
+    }
+
     // 检查是否完成关卡：已生成所有敌方坦克且当前没有存活的敌方坦克
-    let current_enemy_count = enemies.iter().count();
-    if enemy_spawn_state.has_spawned >= enemy_spawn_state.max_count && current_enemy_count == 0 {
-        // 进入下一关
-        stage_level.0 += 1;
-        next_state.set(GameState::StageIntro);
+    // （出生动画中的坦克已计入 has_spawned，但还没有出现，也要等它们出现并被消灭）；
+    // 夺旗模式改为夺回足够次数的旗帜，占山为王模式改为控制分达到目标
+    let current_enemy_count = enemies.iter().count() + born_animations.iter().count();
+    let is_complete = match match_options.active_rule(*game_mode) {
+        MatchRule::CaptureFlag => capture_flag_state.captures >= ctf::FLAG_CAPTURES_TO_WIN,
+        MatchRule::KingOfTheHill => hill_state.score >= kingofthehill::HILL_TARGET_SCORE,
+        MatchRule::Campaign | MatchRule::Survival | MatchRule::TimeAttack => {
+            enemy_spawn_state.has_spawned >= enemy_spawn_state.max_count && current_enemy_count == 0
+        }
+    };
+    if is_complete {
+        // 发放过关奖励
+        results::award_stage_bonus(&mut player_info, &mut stage_stats);
+
+        // 保存玩家属性，带到下一关
+        campaign_run.players.clone_from(&player_info.players);
+        for player_stats in campaign_run.players.values_mut() {
+            buff::end_stage_buffs(player_stats);
+        }
+
+        // 显示关卡结算界面（之后进入下一关）
+        next_state.set(GameState::StageResults);
     }
 }
 
@@ -4691,12 +4640,20 @@ fn reset_for_next_stage(
     playing_entities: Query<Entity, With<PlayingEntity>>,
     mut enemy_spawn_state: ResMut<EnemySpawnState>,
     mut entities_spawned: ResMut<GameEntitiesSpawned>,
+    mut stage_stats: ResMut<StageStats>,
+    mut respawn_timers: ResMut<PlayerRespawnTimers>,
+    mut enemy_freeze: ResMut<EnemyFreezeTimer>,
 ) {
     // 清理所有游戏实体
     for entity in playing_entities.iter() {
         commands.entity(entity).try_despawn();
     }
 
+    // 清空本关统计、等待中的复活和时钟道具的冻结
+    *stage_stats = StageStats::default();
+    respawn_timers.timers.clear();
+    enemy_freeze.timer = None;
+
     // 重置敌方坦克计数
     enemy_spawn_state.has_spawned = 0;
     enemy_spawn_state.spawn_cooldown.reset();
@@ -4776,6 +4733,10 @@ fn enemy_spawn_system(
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     mut enemy_spawn_state: ResMut<EnemySpawnState>,
     enemy_tanks: Query<(), With<EnemyTank>>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    survival_state: Res<SurvivalState>,
+    mut game_rng: ResMut<GameRng>,
 ) {
     // 更新生成冷却时间
     enemy_spawn_state.spawn_cooldown.tick(time.delta());
@@ -4784,16 +4745,20 @@ fn enemy_spawn_system(
     let current_enemy_count = enemy_tanks.iter().count();
 
     // 检查是否需要生成新敌人
-    // 条件：未达到总数上限 + 场上敌人数量少于4个 + 冷却时间已结束
+    // 条件：未达到总数上限 + 场上敌人数量少于同时在场上限 + 冷却时间已结束
     if enemy_spawn_state.has_spawned < enemy_spawn_state.max_count
-        && current_enemy_count < 4
+        && current_enemy_count < enemy_spawn_state.max_alive
         && enemy_spawn_state.spawn_cooldown.is_finished()
     {
-        // 生成敌方坦克出生动画
-        let mut rng = rand::rng();
-        let random_index = rng.random_range(0..ENEMY_BORN_PLACES.len());
+        // 生成敌方坦克出生动画（生存模式按波次选择坦克类型）
+        let random_index = game_rng.random_range(0..ENEMY_BORN_PLACES.len());
         let position = ENEMY_BORN_PLACES[random_index];
-        spawn_enemy_born_animation(&mut commands, &asset_server, &mut texture_atlas_layouts, position);
+        let class = if match_options.active_rule(*game_mode) == MatchRule::Survival {
+            survival::pick_enemy_class(survival_state.wave, &mut game_rng)
+        } else {
+            EnemyClass::Basic
+        };
+        spawn_enemy_born_animation(&mut commands, &asset_server, &mut texture_atlas_layouts, position, class);
 
         // 更新计数
         enemy_spawn_state.has_spawned += 1;
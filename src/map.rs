@@ -99,4 +99,19 @@ pub fn grid_to_world(row: usize, col: usize) -> Vec2 {
     let x = crate::constants::MAP_LEFT_X + col as f32 * GRID_SIZE + GRID_SIZE / 2.0;
     let y = crate::constants::MAP_TOP_Y - row as f32 * GRID_SIZE - GRID_SIZE / 2.0;
     Vec2::new(x, y)
+}
+
+/// 将世界坐标转换为网格坐标（超出地图范围返回 None）
+pub fn world_to_grid(pos: Vec2) -> Option<(usize, usize)> {
+    let col = ((pos.x - crate::constants::MAP_LEFT_X) / GRID_SIZE).floor();
+    let row = ((crate::constants::MAP_TOP_Y - pos.y) / GRID_SIZE).floor();
+    if col < 0.0 || row < 0.0 {
+        return None;
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (row, col) = (row as usize, col as usize);
+    if row >= MAP_ROWS || col >= MAP_COLS {
+        return None;
+    }
+    Some((row, col))
 }
\ No newline at end of file
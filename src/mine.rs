@@ -0,0 +1,173 @@
+//! 地雷系统模块
+//!
+//! 处理玩家布雷：地雷放在坦克所在的格子中央，延时后激活，
+//! 激活后有敌方坦克（玩家可以互相伤害时也包括另一名玩家）进入传感器范围即爆炸，
+//! 布雷的玩家自己和拥有履带链的玩家坦克不会引爆
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::bullet::EffectEvent;
+use crate::combat::{Damage, DamageKind};
+use crate::constants::{
+    EnemyTank, MINE_ARM_DELAY, MINE_MAX_ACTIVE, MINE_SIZE, PlayerTank, PlayingEntity, TEXTURE_MINE,
+    TankType,
+};
+use crate::map::{grid_to_world, world_to_grid};
+use crate::resources::{GameMode, MatchOptions, PlayerInfo};
+
+/// 地雷组件
+#[derive(Component)]
+pub struct Mine {
+    pub owner_type: TankType, // 布雷的玩家
+    pub arm_timer: Timer,     // 激活倒计时，结束前不会被触发
+}
+
+/// 玩家布雷系统
+pub fn player_mine_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    player_tanks: Query<(&Transform, &PlayerTank)>,
+    mines: Query<(&Transform, &Mine)>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+) {
+    for (transform, player_tank) in &player_tanks {
+        // 检查布雷键
+        let mine_key = match player_tank.tank_type {
+            TankType::Player1 => KeyCode::KeyO,
+            TankType::Player2 => KeyCode::Numpad6,
+            TankType::Enemy => continue,
+        };
+        if !keyboard.just_pressed(mine_key) {
+            continue;
+        }
+
+        // 对齐到坦克所在的格子中央
+        let Some((row, col)) = world_to_grid(transform.translation.truncate()) else {
+            continue;
+        };
+        let position = grid_to_world(row, col);
+
+        // 每名玩家同时存在的地雷数量有限，且同一格只能放一颗
+        let active_count = mines.iter().filter(|(_, mine)| mine.owner_type == player_tank.tank_type).count();
+        if active_count >= MINE_MAX_ACTIVE {
+            continue;
+        }
+        let occupied = mines
+            .iter()
+            .any(|(mine_transform, _)| world_to_grid(mine_transform.translation.truncate()) == Some((row, col)));
+        if occupied {
+            continue;
+        }
+
+        let texture: Handle<Image> = asset_server.load(TEXTURE_MINE);
+        commands.spawn((
+            Mine {
+                owner_type: player_tank.tank_type,
+                arm_timer: Timer::from_seconds(MINE_ARM_DELAY, TimerMode::Once),
+            },
+            PlayingEntity,
+            Sprite {
+                image: texture,
+                color: Color::srgba(1.0, 1.0, 1.0, 0.4), // 未激活时半透明
+                custom_size: Some(Vec2::new(MINE_SIZE, MINE_SIZE)),
+                ..default()
+            },
+            Transform::from_xyz(position.x, position.y, 0.1), // 压在坦克下方
+            RigidBody::Fixed,
+            Collider::ball(MINE_SIZE / 2.0),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            ActiveCollisionTypes::all(),
+        ));
+    }
+}
+
+/// 地雷激活系统：倒计时结束后地雷变为不透明
+pub fn update_mine_arming(
+    time: Res<Time>,
+    mut mines: Query<(&mut Mine, &mut Sprite)>,
+) {
+    for (mut mine, mut sprite) in &mut mines {
+        if mine.arm_timer.is_finished() {
+            continue;
+        }
+        mine.arm_timer.tick(time.delta());
+        if mine.arm_timer.is_finished() {
+            sprite.color = sprite.color.with_alpha(1.0);
+        }
+    }
+}
+
+/// 判断驶入地雷的坦克能否引爆它：敌方坦克总是可以；另一名玩家只在玩家可以互相伤害时可以，
+/// 且拥有履带链时免疫；布雷的玩家自己永远不会引爆
+fn triggers_mine(
+    mine: &Mine,
+    tank_entity: Entity,
+    enemy_tanks: &Query<(), With<EnemyTank>>,
+    player_tanks: &Query<&PlayerTank>,
+    player_info: &PlayerInfo,
+    match_options: &MatchOptions,
+    game_mode: GameMode,
+) -> bool {
+    if enemy_tanks.contains(tank_entity) {
+        return true;
+    }
+    let Ok(player_tank) = player_tanks.get(tank_entity) else {
+        return false;
+    };
+    let has_track_chain = player_info
+        .players
+        .get(&player_tank.tank_type)
+        .is_some_and(|stats| stats.track_chain);
+    player_tank.tank_type != mine.owner_type && match_options.players_can_hurt_each_other(game_mode) && !has_track_chain
+}
+
+/// 地雷触发系统：已激活的地雷与可以引爆它的坦克重叠时爆炸
+/// 每帧检查传感器当前的重叠，激活时已经停在地雷上的坦克同样会引爆
+pub fn mine_trigger_system(
+    mut commands: Commands,
+    rapier_context: ReadRapierContext,
+    mut effect_events: MessageWriter<EffectEvent>,
+    mut damage_events: MessageWriter<Damage>,
+    mines: Query<(Entity, &Mine, &Transform)>,
+    enemy_tanks: Query<(), With<EnemyTank>>,
+    player_tanks: Query<&PlayerTank>,
+    player_info: Res<PlayerInfo>,
+    match_options: Res<MatchOptions>,
+    game_mode: Res<GameMode>,
+) {
+    let Ok(context) = rapier_context.single() else {
+        return;
+    };
+
+    for (mine_entity, mine, mine_transform) in &mines {
+        if !mine.arm_timer.is_finished() {
+            continue;
+        }
+
+        // 找到一辆与地雷重叠且可以引爆它的坦克
+        let tank_entity = context
+            .intersection_pairs_with(mine_entity)
+            .filter(|&(_, _, intersecting)| intersecting)
+            .map(|(e1, e2, _)| if e1 == mine_entity { e2 } else { e1 })
+            .find(|&entity| triggers_mine(mine, entity, &enemy_tanks, &player_tanks, &player_info, &match_options, *game_mode));
+        let Some(tank_entity) = tank_entity else {
+            continue;
+        };
+
+        // 踩雷的坦克受到伤害，敌方坦克被击毁时分数记给布雷的玩家
+        damage_events.write(Damage {
+            target: tank_entity,
+            amount: 1,
+            kind: DamageKind::Mine,
+            source: Some(mine.owner_type),
+        });
+
+        // 地雷爆炸
+        effect_events.write(EffectEvent::Explosion {
+            position: mine_transform.translation,
+        });
+        commands.entity(mine_entity).try_despawn();
+    }
+}
@@ -0,0 +1,127 @@
+//! 导弹系统模块
+//!
+//! 处理追踪导弹的发射和制导：导弹以固定转向速度追踪最近的敌方坦克，
+//! 命中、撞到地形、被敌方炮弹击落或超时时爆炸
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::bullet::{spawn_bullet, BulletDespawnMarker, BulletImpactMarker, BulletSpawnParams, ExplosiveShell};
+use crate::constants::{
+    BULLET_SIZE, EnemyTank, MISSILE_LIFETIME, MISSILE_SPEED, MISSILE_TURN_RATE, PlayerTank,
+    RotationTimer, TANK_HEIGHT, TankType,
+};
+use crate::resources::{PlayerInfo, PlayerStatChanged, StatType};
+
+/// 导弹组件：导弹本身也是一颗子弹，复用子弹的碰撞和销毁逻辑
+#[derive(Component)]
+pub struct Missile {
+    pub lifetime: Timer, // 超时后自爆
+}
+
+/// 玩家导弹发射系统
+pub fn player_missile_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    query: Query<(&Transform, &RotationTimer, &PlayerTank)>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut stat_changed_events: MessageWriter<PlayerStatChanged>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+) {
+    for (transform, rotation_timer, player_tank) in &query {
+        // 检查是否正在旋转
+        if rotation_timer.0.elapsed() < rotation_timer.0.duration() {
+            continue;
+        }
+
+        // 检查导弹键（每按一次发射一枚）
+        let missile_key = match player_tank.tank_type {
+            TankType::Player1 => KeyCode::KeyU,
+            TankType::Player2 => KeyCode::Numpad5,
+            TankType::Enemy => continue,
+        };
+        if !keyboard.just_pressed(missile_key) {
+            continue;
+        }
+
+        // 检查导弹数量
+        let Some(player_stats) = player_info.players.get_mut(&player_tank.tank_type) else {
+            continue;
+        };
+        if player_stats.missiles == 0 {
+            continue;
+        }
+        player_stats.missiles -= 1;
+
+        // 计算导弹发射方向（与炮弹相同，基于坦克当前的旋转角度）
+        let euler_angle = transform.rotation.to_euler(EulerRot::XYZ).2;
+        let actual_angle = euler_angle + 90.0_f32.to_radians();
+        let direction = Vec2::new(actual_angle.cos(), actual_angle.sin());
+
+        // 计算导弹初始位置（坦克前方）
+        let missile_pos = transform.translation + direction.extend(0.0) * (TANK_HEIGHT / 2.0 + BULLET_SIZE);
+
+        // 生成导弹（不计入 BulletTracker，不占用炮弹数量）
+        let missile_entity = spawn_bullet(
+            &mut commands,
+            &asset_server,
+            BulletSpawnParams {
+                position: missile_pos,
+                direction,
+                speed: MISSILE_SPEED,
+                owner_type: player_tank.tank_type,
+            },
+        );
+        commands.entity(missile_entity).insert((
+            Missile {
+                lifetime: Timer::from_seconds(MISSILE_LIFETIME, TimerMode::Once),
+            },
+            ExplosiveShell,
+        ));
+
+        stat_changed_events.write(PlayerStatChanged {
+            player_type: player_tank.tank_type,
+            stat_type: StatType::Missile,
+        });
+    }
+}
+
+/// 导弹制导系统：以固定转向速度转向最近的敌方坦克
+pub fn missile_homing_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut missiles: Query<(Entity, &mut Missile, &mut Transform, &mut Velocity), Without<BulletDespawnMarker>>,
+    enemy_tanks: Query<&Transform, (With<EnemyTank>, Without<Missile>)>,
+) {
+    for (entity, mut missile, mut transform, mut velocity) in &mut missiles {
+        // 超时自爆（与命中一样引爆）
+        missile.lifetime.tick(time.delta());
+        if missile.lifetime.is_finished() {
+            commands.entity(entity).try_insert((BulletDespawnMarker, BulletImpactMarker));
+            continue;
+        }
+
+        // 寻找最近的敌方坦克
+        let position = transform.translation.truncate();
+        let nearest = enemy_tanks
+            .iter()
+            .map(|enemy_transform| enemy_transform.translation.truncate())
+            .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)));
+        let Some(target) = nearest else {
+            continue;
+        };
+
+        // 当前方向向目标方向转动，每帧最多转 MISSILE_TURN_RATE * dt 弧度
+        let speed = velocity.linvel.length();
+        let current_angle = velocity.linvel.y.atan2(velocity.linvel.x);
+        let to_target = target - position;
+        let target_angle = to_target.y.atan2(to_target.x);
+        let delta = (target_angle - current_angle + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU) - std::f32::consts::PI;
+        let max_turn = MISSILE_TURN_RATE * time.delta_secs();
+        let new_angle = current_angle + delta.clamp(-max_turn, max_turn);
+
+        velocity.linvel = Vec2::new(new_angle.cos(), new_angle.sin()) * speed;
+        // 与 spawn_bullet 相同：纹理默认向右，按飞行方向旋转
+        transform.rotation = Quat::from_rotation_z(new_angle);
+    }
+}
@@ -0,0 +1,357 @@
+//! 道具系统模块
+//!
+//! 处理道具的定时生成、闪烁和过期消失，以及铁锹道具的司令官堡垒加固
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::constants::{
+    AnimationIndices, AnimationTimer, Brick, COMMANDER_HEIGHT, COMMANDER_WIDTH, CommanderWall,
+    CurrentAnimationFrame, EnemyTank, FORTIFICATION_DURATION, MAP_BOTTOM_Y, PLAYER1_START_POSITION,
+    PLAYER2_START_POSITION, POWERUP_BLINK_TIME, POWERUP_LIFETIME, POWERUP_MAX_ACTIVE,
+    POWERUP_SPAWN_INTERVAL, PlayerTank, PlayingEntity, PowerUp, PowerUpLifetime,
+    STAGE_POWERUP_WEIGHTS, Steel, TANK_WIDTH, TEXTURE_BRICK, TEXTURE_STEEL,
+    VERSUS_COMMANDER1_POSITION, VERSUS_COMMANDER2_POSITION,
+};
+use crate::map::{grid_to_world, world_to_grid, TerrainType, MAP_COLS, MAP_ROWS};
+use crate::resources::{FortificationTimer, GameMode, GameRng, PowerUpDirector};
+
+/// 关卡开始后第一个道具出现的等待时间（秒）
+const FIRST_POWERUP_DELAY: f32 = 3.0;
+
+/// 坦克能否通过该地形（砖块和屏障可被摧毁，视为可到达）
+fn is_reachable_terrain(terrain: TerrainType) -> bool {
+    !matches!(
+        terrain.base_type(),
+        TerrainType::Steel | TerrainType::Sea
+    )
+}
+
+/// 从玩家出生点出发，计算本关所有空闲且可到达的格子
+fn compute_candidate_cells(stage_level: usize) -> Vec<(usize, usize)> {
+    let level_map = crate::levels::get_level(stage_level);
+
+    let mut visited = [[false; MAP_COLS]; MAP_ROWS];
+    let mut queue = VecDeque::new();
+    for start in [PLAYER1_START_POSITION, PLAYER2_START_POSITION] {
+        if let Some((row, col)) = world_to_grid(start.truncate())
+            && !visited[row][col]
+        {
+            visited[row][col] = true;
+            queue.push_back((row, col));
+        }
+    }
+
+    let mut cells = Vec::new();
+    while let Some((row, col)) = queue.pop_front() {
+        // 顶部一行是敌方出生区，底部一行是司令官和玩家出生区，不放道具
+        if level_map[row][col] == TerrainType::Empty && row > 0 && row < MAP_ROWS - 1 {
+            cells.push((row, col));
+        }
+
+        let neighbors = [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ];
+        for (next_row, next_col) in neighbors {
+            if next_row >= MAP_ROWS || next_col >= MAP_COLS || visited[next_row][next_col] {
+                continue;
+            }
+            if is_reachable_terrain(level_map[next_row][next_col]) {
+                visited[next_row][next_col] = true;
+                queue.push_back((next_row, next_col));
+            }
+        }
+    }
+    cells
+}
+
+/// 获取指定关卡的道具权重表
+fn stage_powerup_weights(stage_level: usize) -> &'static [(PowerUp, u32)] {
+    let index = stage_level.saturating_sub(1).min(STAGE_POWERUP_WEIGHTS.len() - 1);
+    STAGE_POWERUP_WEIGHTS[index]
+}
+
+/// 按权重随机选择一个道具类型
+fn pick_powerup_type(weights: &[(PowerUp, u32)], rng: &mut GameRng) -> Option<PowerUp> {
+    let total: u32 = weights.iter().map(|(_, weight)| weight).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = rng.random_range(0..total);
+    for (powerup_type, weight) in weights {
+        if roll < *weight {
+            return Some(*powerup_type);
+        }
+        roll -= weight;
+    }
+    None
+}
+
+/// 关卡开始时重置道具调度器
+pub fn reset_powerup_director(director: &mut PowerUpDirector, stage_level: usize) {
+    director.candidate_cells = compute_candidate_cells(stage_level);
+    director.weights = stage_powerup_weights(stage_level);
+    director.spawn_timer = Timer::from_seconds(POWERUP_SPAWN_INTERVAL, TimerMode::Repeating);
+    // 第一个道具在关卡开始后不久出现
+    director
+        .spawn_timer
+        .set_elapsed(Duration::from_secs_f32(POWERUP_SPAWN_INTERVAL - FIRST_POWERUP_DELAY));
+}
+
+/// 在指定位置批量生成道具
+pub fn spawn_powerup_batch(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    powerup_type: PowerUp,
+    texture_path: &'static str,
+    positions: &[Vec3],
+) {
+    let texture: Handle<Image> = asset_server.load(texture_path);
+    let tile_size = UVec2::new(87, 69);
+    let texture_atlas = TextureAtlasLayout::from_grid(tile_size, 3, 1, None, None);
+    let texture_atlas_layout = texture_atlas_layouts.add(texture_atlas);
+    let animation_indices = AnimationIndices { first: 0, last: 2 };
+
+    for pos in positions {
+        commands.spawn((
+            powerup_type,
+            PlayingEntity,
+            PowerUpLifetime(Timer::from_seconds(POWERUP_LIFETIME, TimerMode::Once)),
+            Sprite::from_atlas_image(
+                texture.clone(),
+                TextureAtlas {
+                    layout: texture_atlas_layout.clone(),
+                    index: animation_indices.first,
+                }
+            ),
+            Transform::from_xyz(pos.x, pos.y, 0.8), // z=0.8 使道具高于除了树之外的所有图层
+            animation_indices,
+            AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+            CurrentAnimationFrame(0),
+            RigidBody::Fixed,
+            Collider::cuboid(87.0 / 2.0, 69.0 / 2.0),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+        ));
+    }
+}
+
+/// 道具调度系统：定时在空闲格子上生成道具
+pub fn powerup_director_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut director: ResMut<PowerUpDirector>,
+    mut rng: ResMut<GameRng>,
+    powerups: Query<(), With<PowerUp>>,
+    occupants: Query<&Transform, Or<(With<Brick>, With<Steel>, With<PowerUp>, With<PlayerTank>, With<EnemyTank>)>>,
+) {
+    director.spawn_timer.tick(time.delta());
+    if !director.spawn_timer.just_finished() {
+        return;
+    }
+
+    // 场上道具已达上限，跳过本次生成
+    if powerups.iter().count() >= POWERUP_MAX_ACTIVE {
+        return;
+    }
+
+    // 排除当前被砖块、钢铁、坦克或其他道具占据的格子
+    let occupied: Vec<(usize, usize)> = occupants
+        .iter()
+        .filter_map(|transform| world_to_grid(transform.translation.truncate()))
+        .collect();
+    let free_cells: Vec<(usize, usize)> = director
+        .candidate_cells
+        .iter()
+        .filter(|cell| !occupied.contains(cell))
+        .copied()
+        .collect();
+    if free_cells.is_empty() {
+        return;
+    }
+
+    let Some(powerup_type) = pick_powerup_type(director.weights, &mut rng) else {
+        return;
+    };
+
+    let (row, col) = free_cells[rng.random_range(0..free_cells.len())];
+    let position = grid_to_world(row, col).extend(0.0);
+
+    spawn_powerup_batch(&mut commands, &asset_server, &mut texture_atlas_layouts, powerup_type, powerup_type.texture_path(), &[position]);
+}
+
+/// 道具过期系统：临近过期时闪烁，超时后移除
+pub fn update_powerup_lifetime(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut PowerUpLifetime, &mut Sprite), With<PowerUp>>,
+) {
+    for (entity, mut lifetime, mut sprite) in &mut query {
+        lifetime.tick(time.delta());
+
+        if lifetime.is_finished() {
+            commands.entity(entity).try_despawn();
+            continue;
+        }
+
+        let remaining = lifetime.remaining_secs();
+        if remaining < POWERUP_BLINK_TIME {
+            // 进入闪烁阶段，越接近过期闪得越快
+            let period = if remaining < POWERUP_BLINK_TIME / 2.0 { 0.2 } else { 0.4 };
+            let visible = remaining % period >= period / 2.0;
+            sprite.color = sprite.color.with_alpha(if visible { 1.0 } else { 0.2 });
+        }
+    }
+}
+
+/// 司令官堡垒墙每块的大小
+const COMMANDER_WALL_BLOCK_SIZE: f32 = 50.0;
+
+/// 司令官堡垒墙各块的位置：左右各 3 块，顶部 2 块封顶
+pub fn commander_wall_positions() -> Vec<Vec2> {
+    let block = COMMANDER_WALL_BLOCK_SIZE;
+    let commander_y = MAP_BOTTOM_Y + COMMANDER_HEIGHT / 2.0;
+    let commander_left = -COMMANDER_WIDTH / 2.0;
+    let commander_right = COMMANDER_WIDTH / 2.0;
+    let commander_top = commander_y + COMMANDER_HEIGHT / 2.0;
+    let commander_bottom = commander_y - COMMANDER_HEIGHT / 2.0;
+
+    let mut positions = Vec::new();
+    for i in 0..3 {
+        let y = (i as f32).mul_add(block, commander_bottom + block / 2.0);
+        positions.push(Vec2::new(commander_left - block / 2.0, y));
+        positions.push(Vec2::new(commander_right + block / 2.0, y));
+    }
+    for i in 0..2 {
+        let x = (i as f32).mul_add(block, -block / 2.0);
+        positions.push(Vec2::new(x, commander_top + block / 2.0));
+    }
+    positions
+}
+
+/// 对战模式司令官堡垒墙各块的位置：司令官靠在地图左右两端，上下各 3 块，朝向地图中间的一侧 2 块
+pub fn versus_commander_wall_positions(commander: Vec3) -> Vec<Vec2> {
+    let block = COMMANDER_WALL_BLOCK_SIZE;
+    // 从地图边缘指向地图中间的方向
+    let inward = if commander.x < 0.0 { 1.0 } else { -1.0 };
+    let edge_x = commander.x - inward * COMMANDER_WIDTH / 2.0;
+    let commander_top = commander.y + COMMANDER_HEIGHT / 2.0;
+    let commander_bottom = commander.y - COMMANDER_HEIGHT / 2.0;
+
+    let mut positions = Vec::new();
+    for i in 0..3 {
+        let x = inward.mul_add((i as f32).mul_add(block, block / 2.0), edge_x);
+        positions.push(Vec2::new(x, commander_top + block / 2.0));
+        positions.push(Vec2::new(x, commander_bottom - block / 2.0));
+    }
+    for i in 0..2 {
+        let y = (i as f32).mul_add(block, commander.y - block / 2.0);
+        positions.push(Vec2::new(edge_x + inward * (COMMANDER_WIDTH + block / 2.0), y));
+    }
+    positions
+}
+
+/// 当前模式下所有司令官堡垒墙的位置：对战模式为两名玩家各自的堡垒，其余模式为底部中间的堡垒
+pub fn commander_wall_positions_for(game_mode: GameMode) -> Vec<Vec2> {
+    if game_mode == GameMode::Versus {
+        [VERSUS_COMMANDER1_POSITION, VERSUS_COMMANDER2_POSITION]
+            .into_iter()
+            .flat_map(versus_commander_wall_positions)
+            .collect()
+    } else {
+        commander_wall_positions()
+    }
+}
+
+/// 生成一块司令官堡垒墙（steel 为 true 时生成钢铁，否则生成砖块）
+pub fn spawn_commander_wall_block(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    position: Vec2,
+    steel: bool,
+) {
+    let block = COMMANDER_WALL_BLOCK_SIZE;
+    let texture: Handle<Image> = asset_server.load(if steel { TEXTURE_STEEL } else { TEXTURE_BRICK });
+    let mut wall = commands.spawn((
+        CommanderWall,
+        PlayingEntity,
+        Sprite {
+            image: texture,
+            custom_size: Some(Vec2::new(block, block)),
+            ..default()
+        },
+        Transform::from_xyz(position.x, position.y, 0.0),
+        RigidBody::Fixed,
+        Collider::cuboid(block / 2.0, block / 2.0),
+        ActiveEvents::COLLISION_EVENTS,
+        ActiveCollisionTypes::all(),
+    ));
+    if steel {
+        wall.insert(Steel);
+    } else {
+        wall.insert(Brick);
+    }
+}
+
+/// 重建整圈司令官堡垒墙：移除剩余的墙块，再在每个位置生成砖块或钢铁
+/// （有坦克停在缺口里的位置不生成，以免把坦克卡在墙里）
+fn rebuild_commander_walls(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    walls: &Query<Entity, With<CommanderWall>>,
+    tank_positions: &[Vec2],
+    steel: bool,
+) {
+    for entity in walls.iter() {
+        commands.entity(entity).try_despawn();
+    }
+    for position in commander_wall_positions() {
+        let blocked = tank_positions
+            .iter()
+            .any(|&tank| crate::build::overlaps(position, COMMANDER_WALL_BLOCK_SIZE / 2.0, tank, TANK_WIDTH / 2.0));
+        if !blocked {
+            spawn_commander_wall_block(commands, asset_server, position, steel);
+        }
+    }
+}
+
+/// 铁锹道具：把司令官周围的墙加固为钢铁（重复拾取刷新时间）
+pub fn fortify_commander_walls(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    walls: &Query<Entity, With<CommanderWall>>,
+    tank_positions: &[Vec2],
+    fortification: &mut FortificationTimer,
+) {
+    rebuild_commander_walls(commands, asset_server, walls, tank_positions, true);
+    fortification.timer = Some(Timer::from_seconds(FORTIFICATION_DURATION, TimerMode::Once));
+}
+
+/// 堡垒加固计时系统：到期后把钢铁墙恢复为砖墙，并补齐被打掉的墙块
+pub fn update_fortification(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    mut fortification: ResMut<FortificationTimer>,
+    walls: Query<Entity, With<CommanderWall>>,
+    tanks: Query<&Transform, Or<(With<PlayerTank>, With<EnemyTank>)>>,
+) {
+    let Some(timer) = fortification.timer.as_mut() else {
+        return;
+    };
+    timer.tick(time.delta());
+    if timer.is_finished() {
+        fortification.timer = None;
+        let tank_positions: Vec<Vec2> = tanks.iter().map(|transform| transform.translation.truncate()).collect();
+        rebuild_commander_walls(&mut commands, &asset_server, &walls, &tank_positions, false);
+    }
+}
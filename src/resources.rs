@@ -1,9 +1,11 @@
 //! Game resources for the Tank Battle game
 
 use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::collections::{HashMap, HashSet};
 
-use crate::constants::TankType;
+use crate::constants::{EnemyClass, PowerUp, TankType, POWERUP_SPAWN_INTERVAL, STAGE_POWERUP_WEIGHTS};
 
 #[derive(Resource, Default)]
 pub struct BulletTracker {
@@ -61,7 +63,7 @@ impl Default for FadingOut {
 
 #[derive(Resource, Default)]
 pub struct CurrentMenuSelection {
-    pub selected_index: usize, // 0 = 1 Player, 1 = 2 Player, 2 = EXIT
+    pub selected_index: usize, // 0 = 1 Player, 1 = 2 Player, 2 = Versus, 3 = About, 4 = Credits, 5 = High Scores, 6 = EXIT
 }
 
 #[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
@@ -69,12 +71,165 @@ pub enum GameMode {
     #[default]
     OnePlayer,
     TwoPlayers,
+    Versus, // 双人对战：各自保卫自己的司令官
+}
+
+// 对局规则（在开始界面切换，只对单人和双人模式生效）
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum MatchRule {
+    #[default]
+    Campaign,      // 战役：依次挑战每一关
+    Survival,      // 生存：敌人一波比一波强，司令官阵亡时结束
+    TimeAttack,    // 限时挑战：依次挑战每一关，按过关用时颁发奖牌
+    CaptureFlag,   // 夺旗：把敌方基地的旗帜带回司令官身边
+    KingOfTheHill, // 占山为王：守住占领区积累控制分
+}
+
+impl MatchRule {
+    /// 开始界面显示的名称
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Campaign => "Campaign",
+            Self::Survival => "Survival",
+            Self::TimeAttack => "Time Attack",
+            Self::CaptureFlag => "Capture the Flag",
+            Self::KingOfTheHill => "King of the Hill",
+        }
+    }
+
+    /// 开始界面按 M 键切换到的下一个规则
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Campaign => Self::Survival,
+            Self::Survival => Self::TimeAttack,
+            Self::TimeAttack => Self::CaptureFlag,
+            Self::CaptureFlag => Self::KingOfTheHill,
+            Self::KingOfTheHill => Self::Campaign,
+        }
+    }
+}
+
+// 对局选项（在开始界面切换）
+#[derive(Resource)]
+pub struct MatchOptions {
+    pub friendly_fire: bool,       // 双人模式下玩家子弹可以伤害队友
+    pub enemy_friendly_fire: bool, // 敌方子弹可以误伤其他敌方坦克
+    pub versus_rounds: usize,      // 对战模式的局数（N 局 N/2+1 胜）
+    pub versus_enemies: bool,      // 对战模式中是否出现中立敌方坦克
+    pub rule: MatchRule,           // 单人和双人模式的对局规则
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        Self {
+            friendly_fire: false,
+            enemy_friendly_fire: false,
+            versus_rounds: 3,
+            versus_enemies: true,
+            rule: MatchRule::Campaign,
+        }
+    }
+}
+
+impl MatchOptions {
+    /// 玩家的炮弹和爆炸能否伤害另一名玩家（对战模式总是可以）
+    pub fn players_can_hurt_each_other(&self, game_mode: GameMode) -> bool {
+        self.friendly_fire || game_mode == GameMode::Versus
+    }
+
+    /// 当前生效的对局规则（对战模式没有规则可选，按战役处理）
+    pub fn active_rule(&self, game_mode: GameMode) -> MatchRule {
+        if game_mode == GameMode::Versus {
+            MatchRule::Campaign
+        } else {
+            self.rule
+        }
+    }
+
+    /// 玩家能否无限复活（对战和生存模式不消耗备用生命）
+    pub fn unlimited_lives(&self, game_mode: GameMode) -> bool {
+        game_mode == GameMode::Versus || self.active_rule(game_mode) == MatchRule::Survival
+    }
+}
+
+// 生存模式的波次进度
+#[derive(Resource)]
+pub struct SurvivalState {
+    pub wave: usize,        // 当前波次（0 表示还没有开始）
+    pub break_timer: Timer, // 两波之间的休整时间
+}
+
+impl Default for SurvivalState {
+    fn default() -> Self {
+        Self {
+            wave: 0,
+            break_timer: Timer::from_seconds(3.0, TimerMode::Once),
+        }
+    }
+}
+
+// 夺旗模式的本关进度
+#[derive(Resource)]
+pub struct CaptureFlagState {
+    pub home: Vec2,         // 旗帜在敌方基地的位置
+    pub captures: usize,    // 本关已经夺回的旗帜数
+    pub hunt_timer: Timer,  // 敌方坦克调整追击方向的间隔
+}
+
+impl Default for CaptureFlagState {
+    fn default() -> Self {
+        Self {
+            home: Vec2::ZERO,
+            captures: 0,
+            hunt_timer: Timer::from_seconds(0.8, TimerMode::Repeating),
+        }
+    }
+}
+
+// 占山为王模式的本关控制分
+#[derive(Resource, Default)]
+pub struct KingOfTheHillState {
+    pub score: usize, // 本关累计的控制分
+}
+
+// 对战模式的比分，只在开始新的对战时清空
+#[derive(Resource)]
+pub struct VersusMatch {
+    pub round: usize,                      // 当前第几局
+    pub wins: HashMap<TankType, usize>,    // 每名玩家赢下的局数
+    pub round_winner: Option<TankType>,    // 上一局的胜者，None 表示平局
+}
+
+impl Default for VersusMatch {
+    fn default() -> Self {
+        Self {
+            round: 1,
+            wins: HashMap::new(),
+            round_winner: None,
+        }
+    }
+}
+
+impl VersusMatch {
+    /// 赢下整场比赛需要的局数
+    pub const fn wins_needed(rounds: usize) -> usize {
+        rounds / 2 + 1
+    }
+
+    /// 已经赢下整场比赛的玩家
+    pub fn match_winner(&self, rounds: usize) -> Option<TankType> {
+        self.wins
+            .iter()
+            .find(|&(_, &wins)| wins >= Self::wins_needed(rounds))
+            .map(|(player_type, _)| *player_type)
+    }
 }
 
 #[derive(Resource)]
 pub struct EnemySpawnState {
     pub has_spawned: usize,    // 已生成数量
-    pub max_count: usize,      // 总数量（每关固定20个）
+    pub max_count: usize,      // 总数量（每关固定20个，生存模式为本波数量）
+    pub max_alive: usize,      // 场上同时存在的最大数量
     pub spawn_cooldown: Timer, // 生成冷却时间
 }
 
@@ -83,6 +238,7 @@ impl Default for EnemySpawnState {
         Self {
             has_spawned: 0,
             max_count: 20,
+            max_alive: 4,
             spawn_cooldown: Timer::from_seconds(0.8, TimerMode::Once),
         }
     }
@@ -107,6 +263,34 @@ pub struct StageIntroTimer {
     pub fade_out: Timer,
 }
 
+// 关卡结算界面的计数动画计时器
+#[derive(Resource, Default)]
+pub struct StageResultsTimer(pub Timer);
+
+// 本关统计（关卡结算界面使用），每关开始时清空
+#[derive(Resource, Default)]
+pub struct StageStats {
+    pub elapsed: f32, // 本关用时（秒），暂停时不计时
+    pub players: HashMap<TankType, PlayerStageStats>,
+}
+
+#[derive(Clone, Default)]
+pub struct PlayerStageStats {
+    pub kills: HashMap<EnemyClass, usize>, // 按敌方坦克类型统计的击毁数
+    pub shots_fired: usize,                // 发射的炮弹数（不含导弹）
+    pub shots_hit: usize,                  // 直接命中敌方坦克的炮弹数
+    pub laser_kills: usize,
+    pub powerups_collected: usize,
+    pub stage_bonus: usize, // 过关奖励分数
+}
+
+impl PlayerStageStats {
+    /// 命中率（百分比）
+    pub fn accuracy(&self) -> usize {
+        (self.shots_hit * 100).checked_div(self.shots_fired).unwrap_or(0)
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct PlayerInfo {
     pub players: HashMap<TankType, PlayerStats>,
@@ -123,9 +307,58 @@ pub struct PlayerStats {
     pub track_chain: bool,
     pub air_cushion: bool,
     pub fire_shell: bool,
+    pub ricochet: bool, // 跳弹：炮弹可在钢铁和外墙上反弹
+    pub missiles: usize, // 追踪导弹剩余数量
+    pub lives: usize, // 备用生命数，生命值归零后消耗一条复活
+    pub extra_lives_awarded: usize, // 已按分数奖励的生命数
     pub life_red_bar: usize, // max 3
     pub energy_blue_bar: usize, // max 3
     pub score: usize,
+    pub buffs: Vec<Buff>, // 当前生效的限时增益
+}
+
+impl PlayerStats {
+    /// 获取指定增益的剩余时间文本（如 " 12s"），没有限时增益时返回空字符串
+    pub fn buff_countdown(&self, stat_type: StatType) -> String {
+        self.buffs
+            .iter()
+            .find(|buff| buff.stat_type == stat_type)
+            .and_then(|buff| buff.timer.as_ref())
+            .map(|timer| format!(" {:.0}s", timer.remaining_secs().ceil()))
+            .unwrap_or_default()
+    }
+}
+
+// 限时增益：记录道具带来的属性变化，到期后撤销
+#[derive(Clone)]
+pub struct Buff {
+    pub stat_type: StatType,  // 增益对应的属性
+    pub amount: usize,        // 数值类增益累计增加的数值（开关类为 0）
+    pub timer: Option<Timer>, // 剩余时间，None 表示永久生效
+}
+
+// 战役进度：跨关卡保留玩家的分数、增益和剩余生命，只在开始新游戏或重新开始时清空
+#[derive(Resource, Default)]
+pub struct CampaignRun {
+    pub players: HashMap<TankType, PlayerStats>, // 上一关结束时的玩家属性
+    pub seed: u32,                               // 本局的种子，开始新游戏时随机生成（初始化 GameRng，记入高分榜）
+}
+
+// 玩法随机数生成器：敌方坦克的转向和射击、敌方坦克出生、道具生成等都从这里取随机数，
+// 开始新游戏时用本局的种子重新初始化（音乐、关卡介绍的俏皮话等表现效果不使用它）
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng(pub StdRng);
+
+impl GameRng {
+    pub fn from_seed(seed: u32) -> Self {
+        Self(StdRng::seed_from_u64(u64::from(seed)))
+    }
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::from_seed(0)
+    }
 }
 
 // 玩家回城计时器
@@ -148,6 +381,32 @@ impl RecallTimer {
     }
 }
 
+// 玩家复活计时器：玩家坦克被摧毁后倒计时，结束时消耗一条生命在出生点复活
+#[derive(Resource, Default)]
+pub struct PlayerRespawnTimers {
+    pub timers: HashMap<TankType, Timer>,
+}
+
+// 双人模式下救援队友的计时器（键为正在救援的玩家坦克）
+#[derive(Resource, Default)]
+pub struct ReviveTimers {
+    pub timers: HashMap<Entity, ReviveTimer>,
+}
+
+pub struct ReviveTimer {
+    pub timer: Timer,
+    pub wreck: Entity, // 正在救援的队友残骸
+}
+
+impl ReviveTimer {
+    pub fn new(wreck: Entity, duration: f32) -> Self {
+        Self {
+            timer: Timer::from_seconds(duration, TimerMode::Once),
+            wreck,
+        }
+    }
+}
+
 // 玩家冲刺计时器
 #[derive(Resource, Default)]
 pub struct DashTimers {
@@ -213,6 +472,9 @@ pub enum StatType {
     Penetrate,
     AirCushion,
     Shell,
+    Ricochet,
+    Missile,
+    Lives,
 }
 
 // Barrier 伤害追踪器，使用冷却机制防止玩家坦克频繁受伤
@@ -229,4 +491,34 @@ pub struct DashDamageTracker {
 
 // 标记游戏实体是否已生成
 #[derive(Resource, Default)]
-pub struct GameEntitiesSpawned(pub bool);
\ No newline at end of file
+pub struct GameEntitiesSpawned(pub bool);
+
+// 道具调度器：按时间在空闲且可到达的格子上生成道具
+#[derive(Resource)]
+pub struct PowerUpDirector {
+    pub spawn_timer: Timer,                     // 生成间隔计时器
+    pub candidate_cells: Vec<(usize, usize)>,   // 本关可生成道具的格子（行，列）
+    pub weights: &'static [(PowerUp, u32)],     // 本关道具权重表
+}
+
+impl Default for PowerUpDirector {
+    fn default() -> Self {
+        Self {
+            spawn_timer: Timer::from_seconds(POWERUP_SPAWN_INTERVAL, TimerMode::Repeating),
+            candidate_cells: Vec::new(),
+            weights: STAGE_POWERUP_WEIGHTS[0],
+        }
+    }
+}
+
+// 司令官堡垒加固计时器（铁锹道具）
+#[derive(Resource, Default)]
+pub struct FortificationTimer {
+    pub timer: Option<Timer>, // None 表示未加固
+}
+
+// 敌方坦克冻结计时器（时钟道具），冻结期间新出现的敌方坦克也会被冻结
+#[derive(Resource, Default)]
+pub struct EnemyFreezeTimer {
+    pub timer: Option<Timer>, // None 表示未冻结
+}
@@ -0,0 +1,242 @@
+//! 关卡结算模块
+//!
+//! 关卡完成后显示每名玩家的本关统计：按敌方坦克类型的击毁数、射击数和命中率、激光击毁数、
+//! 拾取的道具数、用时和过关奖励，数值以动画方式逐步累加，按空格进入下一关
+
+use bevy::prelude::*;
+
+use crate::constants::{EnemyClass, GameState, TankType, WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::resources::{GameMode, PlayerInfo, StageLevel, StageResultsTimer, StageStats};
+
+/// 过关时坦克仍然存活的玩家获得的奖励分数
+pub const STAGE_CLEAR_BONUS: usize = 1000;
+/// 计数动画的时长（秒）
+const TALLY_DURATION: f32 = 2.0;
+
+/// 关卡结算界面的元素
+#[derive(Component)]
+pub struct StageResultsUI;
+
+/// 结算界面中逐步累加的数值
+#[derive(Component)]
+pub struct TallyCounter {
+    pub label: String,
+    pub target: usize,
+    pub suffix: &'static str,
+}
+
+/// 本关计时系统（暂停时不计时）
+pub fn track_stage_time(time: Res<Time>, mut stage_stats: ResMut<StageStats>) {
+    stage_stats.elapsed += time.delta_secs();
+}
+
+/// 过关奖励：坦克仍然存活的玩家获得奖励分数
+pub fn award_stage_bonus(player_info: &mut PlayerInfo, stage_stats: &mut StageStats) {
+    for (player_type, player_stats) in &mut player_info.players {
+        if player_stats.life_red_bar == 0 {
+            continue;
+        }
+        player_stats.score += STAGE_CLEAR_BONUS;
+        stage_stats.players.entry(*player_type).or_default().stage_bonus = STAGE_CLEAR_BONUS;
+    }
+}
+
+/// 生成一行计数文字
+fn spawn_counter(commands: &mut Commands, font: &Handle<Font>, counter: TallyCounter, position: Vec2) {
+    commands.spawn((
+        StageResultsUI,
+        Text2d(format!("{}0{}", counter.label, counter.suffix)),
+        TextFont {
+            font_size: 32.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        Transform::from_xyz(position.x, position.y, 21.0),
+        counter,
+    ));
+}
+
+/// 生成关卡结算界面
+pub fn spawn_stage_results(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    stage_stats: Res<StageStats>,
+    stage_level: Res<StageLevel>,
+    game_mode: Res<GameMode>,
+    player_info: Res<PlayerInfo>,
+    time_attack: Res<crate::timeattack::TimeAttackState>,
+    mut results_timer: ResMut<StageResultsTimer>,
+) {
+    results_timer.0 = Timer::from_seconds(TALLY_DURATION, TimerMode::Once);
+
+    let font: Handle<Font> = asset_server.load(crate::FONT_EN);
+
+    // 黑色背景，盖住战场
+    commands.spawn((
+        StageResultsUI,
+        Sprite {
+            color: Color::srgb(0.0, 0.0, 0.0),
+            custom_size: Some(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 20.0),
+    ));
+
+    // 标题
+    commands.spawn((
+        StageResultsUI,
+        Text2d(format!("Stage {} Clear", stage_level.0)),
+        TextFont {
+            font_size: 80.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.8, 0.0)),
+        Transform::from_xyz(0.0, 420.0, 21.0),
+    ));
+
+    // 本关用时
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, reason = "用时取整后非负，远小于 usize 的上限")]
+    let elapsed_seconds = stage_stats.elapsed.round().max(0.0) as usize;
+    spawn_counter(
+        &mut commands,
+        &font,
+        TallyCounter {
+            label: "Time: ".to_string(),
+            target: elapsed_seconds,
+            suffix: "s",
+        },
+        Vec2::new(0.0, 320.0),
+    );
+
+    // 每名玩家一列
+    let columns: &[(TankType, f32)] = match *game_mode {
+        GameMode::OnePlayer => &[(TankType::Player1, 0.0)],
+        GameMode::TwoPlayers | GameMode::Versus => &[(TankType::Player1, -450.0), (TankType::Player2, 450.0)],
+    };
+    for &(player_type, x) in columns {
+        let stats = stage_stats.players.get(&player_type).cloned().unwrap_or_default();
+
+        let name = player_info
+            .players
+            .get(&player_type)
+            .map(|player_stats| player_stats.name.clone())
+            .unwrap_or_default();
+        commands.spawn((
+            StageResultsUI,
+            Text2d(name),
+            TextFont {
+                font_size: 44.0,
+                font: font.clone(),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            Transform::from_xyz(x, 220.0, 21.0),
+        ));
+
+        let rows = EnemyClass::ALL
+            .iter()
+            .map(|class| TallyCounter {
+                label: format!("{}: ", class.label()),
+                target: stats.kills.get(class).copied().unwrap_or(0),
+                suffix: "",
+            })
+            .chain([
+            TallyCounter { label: "Shots fired: ".to_string(), target: stats.shots_fired, suffix: "" },
+            TallyCounter { label: "Accuracy: ".to_string(), target: stats.accuracy(), suffix: "%" },
+            TallyCounter { label: "Laser kills: ".to_string(), target: stats.laser_kills, suffix: "" },
+            TallyCounter { label: "Power-ups: ".to_string(), target: stats.powerups_collected, suffix: "" },
+            TallyCounter { label: "Stage bonus: ".to_string(), target: stats.stage_bonus, suffix: "" },
+        ]);
+        for (index, counter) in rows.enumerate() {
+            spawn_counter(&mut commands, &font, counter, Vec2::new(x, (index as f32).mul_add(-60.0, 140.0)));
+        }
+    }
+
+    // 限时挑战：奖牌和最好成绩
+    if let Some(result) = time_attack.last_result {
+        let best_text = if result.is_new_record() {
+            "New Record!".to_string()
+        } else {
+            format!("Best {}", crate::timeattack::format_time(result.previous_best.unwrap_or(result.time)))
+        };
+        commands.spawn((
+            StageResultsUI,
+            Text2d(format!(
+                "{}   {}   {}",
+                result.medal.label(),
+                crate::timeattack::format_time(result.time),
+                best_text
+            )),
+            TextFont {
+                font_size: 40.0,
+                font: font.clone(),
+                ..default()
+            },
+            TextColor(result.medal.color()),
+            Transform::from_xyz(0.0, -340.0, 21.0),
+        ));
+    }
+
+    // 操作说明
+    commands.spawn((
+        StageResultsUI,
+        Text2d("SPACE to continue".to_string()),
+        TextFont {
+            font_size: 30.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        Transform::from_xyz(0.0, -400.0, 21.0),
+    ));
+}
+
+/// 计数动画系统：所有数值在 `TALLY_DURATION` 内从 0 累加到目标值
+pub fn animate_stage_results(
+    time: Res<Time>,
+    mut results_timer: ResMut<StageResultsTimer>,
+    mut counters: Query<(&TallyCounter, &mut Text2d)>,
+) {
+    results_timer.0.tick(time.delta());
+    // 用整数毫秒按比例累加，避免浮点数转换截断
+    let elapsed_ms = results_timer.0.elapsed().as_millis();
+    let duration_ms = results_timer.0.duration().as_millis().max(1);
+    for (counter, mut text) in &mut counters {
+        let value = usize::try_from(counter.target as u128 * elapsed_ms / duration_ms).unwrap_or(counter.target);
+        text.0 = format!("{}{}{}", counter.label, value, counter.suffix);
+    }
+}
+
+/// 结算界面输入：计数未完成时空格跳过动画，完成后空格进入下一关
+pub fn handle_stage_results_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut results_timer: ResMut<StageResultsTimer>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut stage_level: ResMut<StageLevel>,
+) {
+    // Esc 键退出
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        std::process::exit(0);
+    }
+
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+    if !results_timer.0.is_finished() {
+        let duration = results_timer.0.duration();
+        results_timer.0.set_elapsed(duration);
+        return;
+    }
+
+    // 进入下一关
+    stage_level.0 += 1;
+    next_state.set(GameState::StageIntro);
+}
+
+/// 清理关卡结算界面
+pub fn despawn_stage_results(mut commands: Commands, query: Query<Entity, With<StageResultsUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).try_despawn();
+    }
+}
@@ -0,0 +1,252 @@
+//! 队友救援模块
+//!
+//! 玩家坦克被摧毁后在原地留下残骸。双人模式下队友的备用生命用完后，存活的玩家可以在残骸旁
+//! 按住救援键读条救回队友（进度条显示在残骸上方，受到伤害、松开按键或离开残骸都会打断），
+//! 被救回的玩家在离残骸最近的空位复活，只有 1 点生命值
+
+use bevy::prelude::*;
+use std::collections::HashSet;
+
+use crate::combat::Damage;
+use crate::build::overlaps;
+use crate::constants::{
+    BRICK_WIDTH, Barrier, Brick, COMMANDER_WIDTH, Commander, EnemyTank, PlayerAvatar, PlayerDead,
+    PlayerTank, PlayerUI, PlayingEntity, SOUND_POWERUP, Sea, Steel, TANK_HEIGHT, TANK_WIDTH,
+    TEXTURE_PLAYER_TANK1, TEXTURE_PLAYER_TANK2, TankType,
+};
+use crate::map::{grid_to_world, GRID_SIZE, MAP_COLS, MAP_ROWS};
+use crate::resources::{GameMode, PlayerInfo, ReviveTimer, ReviveTimers};
+
+/// 救援读条时间（秒）
+const REVIVE_TIME: f32 = 3.0;
+/// 救援时与残骸的最大距离（像素）
+const REVIVE_RANGE: f32 = 120.0;
+/// 被救回的玩家的生命值
+const REVIVE_HEALTH: usize = 1;
+
+/// 玩家坦克的残骸
+#[derive(Component)]
+pub struct PlayerWreck {
+    pub player_type: TankType,
+}
+
+/// 救援进度条（在残骸上方）
+#[derive(Component)]
+pub struct ReviveProgressBar {
+    pub reviver: Entity,
+}
+
+/// 在玩家坦克被摧毁的位置留下残骸
+pub fn spawn_player_wreck(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    player_type: TankType,
+    transform: &Transform,
+) {
+    let texture = match player_type {
+        TankType::Player1 => TEXTURE_PLAYER_TANK1,
+        TankType::Player2 => TEXTURE_PLAYER_TANK2,
+        TankType::Enemy => return,
+    };
+    let (texture_atlas_layout, animation_indices) = crate::player_tank_atlas(texture_atlas_layouts);
+    commands.spawn((
+        PlayerWreck { player_type },
+        PlayingEntity,
+        Sprite {
+            image: asset_server.load(texture),
+            texture_atlas: Some(TextureAtlas {
+                layout: texture_atlas_layout,
+                index: animation_indices.first,
+            }),
+            color: Color::srgba(0.3, 0.3, 0.3, 0.8), // 烧黑的残骸
+            custom_size: Some(Vec2::new(80.0, 90.0)),
+            ..default()
+        },
+        Transform {
+            translation: transform.translation.with_z(-0.1), // 在坦克下方
+            rotation: transform.rotation,
+            ..default()
+        },
+    ));
+}
+
+/// 救援键（玩家1：Y，玩家2：小键盘8）
+fn is_revive_key_pressed(keyboard_input: &ButtonInput<KeyCode>, tank_type: TankType) -> bool {
+    if tank_type == TankType::Player1 {
+        keyboard_input.pressed(KeyCode::KeyY)
+    } else {
+        keyboard_input.pressed(KeyCode::Numpad8)
+    }
+}
+
+/// 开始救援：双人模式下按住救援键且附近有备用生命用完的队友残骸
+pub fn handle_revive_input(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    game_mode: Res<GameMode>,
+    player_info: Res<PlayerInfo>,
+    player_tanks: Query<(Entity, &Transform, &PlayerTank)>,
+    wrecks: Query<(Entity, &Transform, &PlayerWreck)>,
+    mut revive_timers: ResMut<ReviveTimers>,
+) {
+    if *game_mode != GameMode::TwoPlayers {
+        return;
+    }
+
+    for (entity, transform, player_tank) in &player_tanks {
+        if revive_timers.timers.contains_key(&entity) || !is_revive_key_pressed(&keyboard_input, player_tank.tank_type) {
+            continue;
+        }
+
+        let wreck = wrecks.iter().find(|(wreck_entity, wreck_transform, wreck)| {
+            let partner_is_out = player_info
+                .players
+                .get(&wreck.player_type)
+                .is_some_and(|stats| stats.life_red_bar == 0 && stats.lives == 0);
+            let in_range = transform.translation.truncate().distance(wreck_transform.translation.truncate()) <= REVIVE_RANGE;
+            let already_reviving = revive_timers.timers.values().any(|timer| timer.wreck == *wreck_entity);
+            wreck.player_type != player_tank.tank_type && partner_is_out && in_range && !already_reviving
+        });
+        let Some((wreck_entity, wreck_transform, _)) = wreck else {
+            continue;
+        };
+
+        // 开始救援
+        revive_timers.timers.insert(entity, ReviveTimer::new(wreck_entity, REVIVE_TIME));
+
+        // 创建救援进度条（在残骸正上方，初始为空）
+        commands.spawn((
+            PlayingEntity,
+            ReviveProgressBar { reviver: entity },
+            Sprite {
+                color: Color::srgb(0.0, 1.0, 0.0), // 绿色
+                custom_size: Some(Vec2::new(0.0, 8.0)),
+                ..default()
+            },
+            Transform::from_xyz(wreck_transform.translation.x, wreck_transform.translation.y + TANK_HEIGHT / 2.0 + 20.0, 2.0),
+        ));
+    }
+}
+
+/// 找到离残骸最近、不与地形、坦克和司令官重叠的复活位置（与砌砖相同的重叠判定）：
+/// 先尝试残骸所在的位置，再按距离依次尝试地图上的格子中央
+fn find_revive_position(
+    wreck_position: Vec2,
+    terrain: &Query<(&Transform, Has<Sea>, Has<Barrier>), Or<(With<Brick>, With<Steel>, With<Sea>, With<Barrier>)>>,
+    tanks: &Query<&Transform, Or<(With<PlayerTank>, With<EnemyTank>)>>,
+    commanders: &Query<&Transform, With<Commander>>,
+) -> Option<Vec2> {
+    let tank_half = TANK_WIDTH / 2.0;
+    let is_free = |position: Vec2| {
+        !terrain.iter().any(|(t, is_sea, is_barrier)| {
+            let half = if is_sea || is_barrier { GRID_SIZE / 2.0 } else { BRICK_WIDTH / 2.0 };
+            overlaps(position, tank_half, t.translation.truncate(), half)
+        }) && !tanks.iter().any(|t| overlaps(position, tank_half, t.translation.truncate(), TANK_WIDTH / 2.0))
+            && !commanders.iter().any(|t| overlaps(position, tank_half, t.translation.truncate(), COMMANDER_WIDTH / 2.0))
+    };
+
+    let mut cells: Vec<Vec2> = (0..MAP_ROWS)
+        .flat_map(|row| (0..MAP_COLS).map(move |col| grid_to_world(row, col)))
+        .collect();
+    cells.sort_by(|a, b| a.distance_squared(wreck_position).total_cmp(&b.distance_squared(wreck_position)));
+    std::iter::once(wreck_position).chain(cells).find(|&position| is_free(position))
+}
+
+/// 救援读条：松开按键、离开残骸或受到伤害时打断，读条完成后队友在离残骸最近的空位复活
+/// （附近没有空位时保持满格，等到有空位再复活）
+pub fn update_revive_timers(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut damage_events: MessageReader<Damage>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut revive_timers: ResMut<ReviveTimers>,
+    player_tanks: Query<(&Transform, &PlayerTank)>,
+    wrecks: Query<(&Transform, &PlayerWreck)>,
+    mut progress_bars: Query<(Entity, &mut Sprite, &ReviveProgressBar), Without<PlayerAvatar>>,
+    mut player_avatars: Query<(Entity, &PlayerUI, &mut Sprite), (With<PlayerAvatar>, With<PlayerDead>)>,
+    terrain: Query<(&Transform, Has<Sea>, Has<Barrier>), Or<(With<Brick>, With<Steel>, With<Sea>, With<Barrier>)>>,
+    tanks: Query<&Transform, Or<(With<PlayerTank>, With<EnemyTank>)>>,
+    commanders: Query<&Transform, With<Commander>>,
+) {
+    // 本帧受到伤害的坦克
+    let damaged: HashSet<Entity> = damage_events.read().map(|event| event.target).collect();
+
+    let revivers: Vec<Entity> = revive_timers.timers.keys().copied().collect();
+    for reviver in revivers {
+        let Some(revive_timer) = revive_timers.timers.get_mut(&reviver) else {
+            continue;
+        };
+        let wreck = wrecks.get(revive_timer.wreck).ok();
+
+        let is_interrupted = match (player_tanks.get(reviver), wreck) {
+            (Ok((transform, player_tank)), Some((wreck_transform, _))) => {
+                !is_revive_key_pressed(&keyboard_input, player_tank.tank_type)
+                    || damaged.contains(&reviver)
+                    || transform.translation.truncate().distance(wreck_transform.translation.truncate()) > REVIVE_RANGE
+            }
+            // 救援者被摧毁或残骸已经消失
+            _ => true,
+        };
+
+        if !is_interrupted {
+            revive_timer.timer.tick(time.delta());
+
+            // 更新进度条（从空递增到满格）
+            let bar_width = 100.0 * revive_timer.timer.fraction();
+            for (_, mut sprite, progress_bar) in &mut progress_bars {
+                if progress_bar.reviver == reviver {
+                    sprite.custom_size = Some(Vec2::new(bar_width, 8.0));
+                }
+            }
+
+            if !revive_timer.timer.is_finished() {
+                continue;
+            }
+
+            // 救援完成：队友在离残骸最近的空位复活
+            let wreck_entity = revive_timer.wreck;
+            if let Some((wreck_transform, wreck)) = wreck
+                && player_info.players.contains_key(&wreck.player_type)
+            {
+                let Some(position) = find_revive_position(wreck_transform.translation.truncate(), &terrain, &tanks, &commanders) else {
+                    continue;
+                };
+                if let Some(partner_stats) = player_info.players.get_mut(&wreck.player_type) {
+                    partner_stats.life_red_bar = REVIVE_HEALTH;
+                }
+
+                crate::lives::spawn_revived_tank(
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlas_layouts,
+                    wreck.player_type,
+                    position.extend(0.0),
+                );
+                crate::lives::restore_player_avatar(
+                    &mut commands,
+                    &asset_server,
+                    &mut texture_atlas_layouts,
+                    &mut player_avatars,
+                    wreck.player_type,
+                );
+                commands.entity(wreck_entity).try_despawn();
+
+                // 播放道具音效
+                let powerup_sound: Handle<AudioSource> = asset_server.load(SOUND_POWERUP);
+                commands.spawn(AudioPlayer::new(powerup_sound));
+            }
+        }
+
+        // 结束救援（完成或被打断），删除进度条
+        revive_timers.timers.remove(&reviver);
+        for (progress_entity, _, progress_bar) in progress_bars.iter() {
+            if progress_bar.reviver == reviver {
+                commands.entity(progress_entity).try_despawn();
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! 生存模式模块
+//!
+//! 生存模式没有 20 辆敌方坦克的上限，敌人按波次出现：每一波数量更多、同时在场的更多、
+//! 出生间隔更短，后续波次还会出现快速和重甲坦克。玩家可以无限复活，司令官阵亡时游戏结束，
+//! 分数和到达的波次记入高分榜
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::constants::{EnemyBornAnimation, EnemyClass, EnemyTank};
+use crate::resources::{
+    EnemySpawnState, GameMode, GameRng, MatchOptions, MatchRule, SurvivalState,
+};
+
+/// 第一波的敌方坦克数量
+const FIRST_WAVE_SIZE: usize = 10;
+/// 每一波比上一波多出的敌方坦克数量
+const WAVE_SIZE_STEP: usize = 5;
+/// 场上同时存在的敌方坦克上限
+const MAX_ALIVE_LIMIT: usize = 12;
+/// 出生间隔的下限（秒）
+const MIN_SPAWN_COOLDOWN: f32 = 0.2;
+
+/// 顶部显示的波次文字
+#[derive(Component)]
+pub struct SurvivalWaveText;
+
+/// 开始指定的波次：设置本波数量、同时在场上限和出生间隔
+pub fn start_wave(survival_state: &mut SurvivalState, enemy_spawn_state: &mut EnemySpawnState, wave: usize) {
+    survival_state.wave = wave;
+    survival_state.break_timer.reset();
+
+    let step = wave - 1;
+    enemy_spawn_state.has_spawned = 0;
+    enemy_spawn_state.max_count = FIRST_WAVE_SIZE + WAVE_SIZE_STEP * step;
+    enemy_spawn_state.max_alive = (4 + step / 2).min(MAX_ALIVE_LIMIT);
+    let cooldown = 0.06f32.mul_add(-(step as f32), 0.8).max(MIN_SPAWN_COOLDOWN);
+    enemy_spawn_state.spawn_cooldown = Timer::from_seconds(cooldown, TimerMode::Once);
+}
+
+/// 按波次随机选择敌方坦克类型：越往后快速和重甲坦克越多
+pub fn pick_enemy_class(wave: usize, rng: &mut GameRng) -> EnemyClass {
+    let step = wave.saturating_sub(1) as f32;
+    let armored_chance = (0.05 * step).min(0.4);
+    let fast_chance = (0.08 * step).min(0.35);
+
+    let roll = rng.random::<f32>();
+    if roll < armored_chance {
+        EnemyClass::Armored
+    } else if roll < armored_chance + fast_chance {
+        EnemyClass::Fast
+    } else {
+        EnemyClass::Basic
+    }
+}
+
+/// 波次系统：本波敌人全部出现并被消灭后休整几秒，然后开始下一波
+pub fn update_survival_waves(
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    mut survival_state: ResMut<SurvivalState>,
+    mut enemy_spawn_state: ResMut<EnemySpawnState>,
+    enemies: Query<(), With<EnemyTank>>,
+    born_animations: Query<(), With<EnemyBornAnimation>>,
+    mut wave_texts: Query<&mut Text2d, With<SurvivalWaveText>>,
+) {
+    if match_options.active_rule(*game_mode) != MatchRule::Survival {
+        return;
+    }
+
+    let current_enemy_count = enemies.iter().count() + born_animations.iter().count();
+    if enemy_spawn_state.has_spawned >= enemy_spawn_state.max_count && current_enemy_count == 0 {
+        survival_state.break_timer.tick(time.delta());
+        if survival_state.break_timer.is_finished() {
+            let next_wave = survival_state.wave + 1;
+            start_wave(&mut survival_state, &mut enemy_spawn_state, next_wave);
+        }
+    }
+
+    for mut text in &mut wave_texts {
+        text.0 = format!("Wave {}", survival_state.wave);
+    }
+}
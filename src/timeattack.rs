@@ -0,0 +1,231 @@
+//! 限时挑战模块
+//!
+//! 限时挑战规则下依次挑战每一关，顶部显示本关用时。关卡文件声明了标准时间（@par）和金牌时间（@gold），
+//! 过关时按用时颁发奖牌，每关的最好成绩保存在用户数据目录下。游戏中按 Backspace 立即重开本关，
+//! 不再经过关卡介绍
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+
+use crate::constants::{GameOverTimer, PlayingEntity};
+use crate::resources::{
+    CommanderLife, EnemyFreezeTimer, EnemySpawnState, GameEntitiesSpawned, GameMode, MatchOptions,
+    MatchRule, PlayerInfo, PlayerRespawnTimers, StageLevel, StageStats,
+};
+
+/// 最好成绩文件名（在用户数据目录下）
+const BEST_TIMES_FILE: &str = "best_times.txt";
+
+/// 奖牌
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Medal {
+    Gold,   // 不超过金牌时间
+    Silver, // 不超过标准时间
+    Bronze, // 超过标准时间
+}
+
+impl Medal {
+    /// 按用时颁发奖牌，关卡没有声明对应时间时按更低一级处理
+    pub fn for_time(time: f32, par_time: Option<f32>, gold_time: Option<f32>) -> Self {
+        if gold_time.is_some_and(|gold| time <= gold) {
+            Self::Gold
+        } else if par_time.is_some_and(|par| time <= par) {
+            Self::Silver
+        } else {
+            Self::Bronze
+        }
+    }
+
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Gold => "Gold Medal",
+            Self::Silver => "Silver Medal",
+            Self::Bronze => "Bronze Medal",
+        }
+    }
+
+    pub const fn color(self) -> Color {
+        match self {
+            Self::Gold => Color::srgb(1.0, 0.8, 0.0),
+            Self::Silver => Color::srgb(0.8, 0.8, 0.85),
+            Self::Bronze => Color::srgb(0.8, 0.5, 0.2),
+        }
+    }
+}
+
+/// 一关的过关成绩（结算界面显示）
+#[derive(Clone, Copy)]
+pub struct StageTimeResult {
+    pub time: f32,
+    pub medal: Medal,
+    pub previous_best: Option<f32>,
+}
+
+impl StageTimeResult {
+    pub fn is_new_record(&self) -> bool {
+        self.previous_best.is_none_or(|best| self.time < best)
+    }
+}
+
+/// 限时挑战状态
+#[derive(Resource, Default)]
+pub struct TimeAttackState {
+    pub par_time: Option<f32>,           // 本关的标准时间
+    pub gold_time: Option<f32>,          // 本关的金牌时间
+    pub best_times: HashMap<usize, f32>, // 每关的最好成绩（秒）
+    pub last_result: Option<StageTimeResult>,
+}
+
+/// 顶部显示的本关用时
+#[derive(Component)]
+pub struct StageTimerText;
+
+/// 把秒数格式化为 分:秒.十分之一秒
+pub fn format_time(seconds: f32) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, reason = "用时截断到十分之一秒，负数按 0 处理")]
+    let tenths = (seconds * 10.0).max(0.0) as usize;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+/// 读取每关的最好成绩，文件不存在时返回空表
+pub fn load_time_attack_state() -> TimeAttackState {
+    let mut state = TimeAttackState::default();
+    let Some(path) = crate::highscores::user_data_file(BEST_TIMES_FILE) else {
+        return state;
+    };
+    let Ok(content) = fs::read_to_string(&path) else {
+        return state;
+    };
+
+    for line in content.lines() {
+        // 跳过空行和注释
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parsed = line
+            .split_once('\t')
+            .and_then(|(level, time)| Some((level.parse::<usize>().ok()?, time.parse::<f32>().ok()?)));
+        match parsed {
+            Some((level, time)) => {
+                state.best_times.insert(level, time);
+            }
+            None => eprintln!("Warning: Skipping invalid best time line in {}: {}", path.display(), line),
+        }
+    }
+    state
+}
+
+/// 写入每关的最好成绩
+fn save_best_times(best_times: &HashMap<usize, f32>) {
+    let Some(path) = crate::highscores::user_data_file(BEST_TIMES_FILE) else {
+        eprintln!("Warning: No user data directory, best times are not saved");
+        return;
+    };
+
+    let mut levels: Vec<_> = best_times.iter().collect();
+    levels.sort_by_key(|&(level, _)| *level);
+    let mut content = String::from("# level\tseconds\n");
+    for (level, time) in levels {
+        let _ = writeln!(content, "{level}\t{time}");
+    }
+
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&path, content));
+    if let Err(e) = result {
+        eprintln!("Warning: Failed to save best times to {}: {}", path.display(), e);
+    }
+}
+
+/// 进入关卡介绍时读取本关的标准时间和金牌时间
+pub fn load_stage_times(stage_level: Res<StageLevel>, mut time_attack: ResMut<TimeAttackState>) {
+    let meta = crate::levels::get_level_meta(stage_level.0);
+    time_attack.par_time = meta.par_time;
+    time_attack.gold_time = meta.gold_time;
+}
+
+/// 更新顶部的本关用时：金牌时间内为金色，标准时间内为白色，超时为红色
+pub fn update_stage_timer_text(
+    stage_stats: Res<StageStats>,
+    time_attack: Res<TimeAttackState>,
+    mut timer_texts: Query<(&mut Text2d, &mut TextColor), With<StageTimerText>>,
+) {
+    let elapsed = stage_stats.elapsed;
+    for (mut text, mut text_color) in &mut timer_texts {
+        text.0 = time_attack.par_time.map_or_else(
+            || format!("Time {}", format_time(elapsed)),
+            |par| format!("Time {} / Par {}", format_time(elapsed), format_time(par)),
+        );
+        text_color.0 = match Medal::for_time(elapsed, time_attack.par_time, time_attack.gold_time) {
+            Medal::Gold => Medal::Gold.color(),
+            Medal::Silver => Color::srgb(1.0, 1.0, 1.0),
+            Medal::Bronze => Color::srgb(1.0, 0.3, 0.3),
+        };
+    }
+}
+
+/// 过关时记录成绩：颁发奖牌，刷新并保存本关的最好成绩
+pub fn record_stage_time(
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    stage_level: Res<StageLevel>,
+    stage_stats: Res<StageStats>,
+    mut time_attack: ResMut<TimeAttackState>,
+) {
+    if match_options.active_rule(*game_mode) != MatchRule::TimeAttack {
+        time_attack.last_result = None;
+        return;
+    }
+
+    let time = stage_stats.elapsed;
+    let result = StageTimeResult {
+        time,
+        medal: Medal::for_time(time, time_attack.par_time, time_attack.gold_time),
+        previous_best: time_attack.best_times.get(&stage_level.0).copied(),
+    };
+    if result.is_new_record() {
+        time_attack.best_times.insert(stage_level.0, time);
+        save_best_times(&time_attack.best_times);
+    }
+    time_attack.last_result = Some(result);
+}
+
+/// 立即重开本关（Backspace）：清理战场，恢复到本关开始时的状态，重新生成游戏实体
+pub fn handle_time_attack_restart(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    game_mode: Res<GameMode>,
+    match_options: Res<MatchOptions>,
+    playing_entities: Query<Entity, Or<(With<PlayingEntity>, With<GameOverTimer>)>>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut commander_life: ResMut<CommanderLife>,
+    mut enemy_spawn_state: ResMut<EnemySpawnState>,
+    mut stage_stats: ResMut<StageStats>,
+    mut respawn_timers: ResMut<PlayerRespawnTimers>,
+    mut enemy_freeze: ResMut<EnemyFreezeTimer>,
+    mut entities_spawned: ResMut<GameEntitiesSpawned>,
+) {
+    if match_options.active_rule(*game_mode) != MatchRule::TimeAttack || !keyboard_input.just_pressed(KeyCode::Backspace) {
+        return;
+    }
+
+    // 清理所有游戏实体（包括等待中的 Game Over）
+    for entity in playing_entities.iter() {
+        commands.entity(entity).try_despawn();
+    }
+
+    // 玩家属性在重新生成时从战役进度恢复，本关统计和计时清零
+    player_info.players.clear();
+    commander_life.life_red_bar = 3;
+    *stage_stats = StageStats::default();
+    respawn_timers.timers.clear();
+    enemy_freeze.timer = None;
+    enemy_spawn_state.has_spawned = 0;
+    enemy_spawn_state.spawn_cooldown.reset();
+
+    // 由 spawn_game_entities_if_needed 重新生成游戏实体
+    entities_spawned.0 = false;
+}
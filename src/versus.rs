@@ -0,0 +1,269 @@
+//! 对战模式模块
+//!
+//! 两名玩家各守一个司令官，先摧毁对方司令官的玩家赢下这一局（同时被摧毁算平局），
+//! 先赢下过半局数的玩家赢得整场比赛。每局结束后显示比分，按空格进入下一局
+
+use bevy::prelude::*;
+
+use crate::constants::{
+    Commander, CommanderOwner, GameState, Health, PlayingEntity, TankType, WINDOW_HEIGHT,
+    WINDOW_LEFT_X, WINDOW_RIGHT_X, WINDOW_TOP_Y, WINDOW_WIDTH,
+};
+use crate::resources::{GameMode, MatchOptions, VersusMatch};
+
+/// 司令官被摧毁后到显示比分的延迟（秒），等待爆炸动画完成
+const ROUND_OVER_DELAY: f32 = 1.2;
+
+/// 对战模式顶部的基地血条
+#[derive(Component)]
+pub struct VersusBaseHealthBar {
+    pub owner: TankType,
+    pub original_x: f32,
+}
+
+/// 本局结束后等待显示比分的计时器
+#[derive(Component)]
+pub struct RoundOverTimer(pub Timer);
+
+/// 对战比分界面的元素
+#[derive(Component)]
+pub struct VersusResultsUI;
+
+const fn player_label(player_type: TankType) -> &'static str {
+    match player_type {
+        TankType::Player1 => "Player 1",
+        TankType::Player2 => "Player 2",
+        TankType::Enemy => "Enemy",
+    }
+}
+
+/// 生成对战模式的顶部信息：局数、比分和双方基地血条
+pub fn spawn_versus_hud(commands: &mut Commands, font: &Handle<Font>, versus_match: &VersusMatch, rounds: usize) {
+    let wins = |player_type| versus_match.wins.get(&player_type).copied().unwrap_or(0);
+
+    // 局数和比分显示在顶部中心
+    commands.spawn((
+        PlayingEntity,
+        Text2d(format!(
+            "Round {} (Best of {})   {} : {}",
+            versus_match.round,
+            rounds,
+            wins(TankType::Player1),
+            wins(TankType::Player2)
+        )),
+        TextFont {
+            font_size: 28.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 0.0)), // 黄色
+        Transform::from_xyz(0.0, WINDOW_TOP_Y - 50.0, 1.0),
+    ));
+
+    // 双方基地血条分别在左右两侧，与各自的司令官同侧
+    for (owner, text_x) in [(TankType::Player1, WINDOW_LEFT_X + 393.0), (TankType::Player2, WINDOW_RIGHT_X - 607.0)] {
+        commands.spawn((
+            PlayingEntity,
+            Text2d(format!("{} Base:", player_label(owner))),
+            TextFont {
+                font_size: 28.0,
+                font: font.clone(),
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 1.0, 1.0)),
+            Transform::from_xyz(text_x, WINDOW_TOP_Y - 50.0, 1.0),
+        ));
+        let bar_x = text_x + 214.0; // 文字右侧
+        commands.spawn((
+            PlayingEntity,
+            VersusBaseHealthBar { owner, original_x: bar_x },
+            Sprite {
+                color: Color::srgb(1.0, 0.0, 0.0),
+                custom_size: Some(Vec2::new(160.0, 10.0)),
+                ..default()
+            },
+            Transform::from_xyz(bar_x, WINDOW_TOP_Y - 50.0, 1.0),
+        ));
+    }
+}
+
+/// 基地血条更新系统：按对应司令官的生命值缩短血条（从右向左）
+pub fn update_versus_base_health_bars(
+    commanders: Query<(&Health, &CommanderOwner), With<Commander>>,
+    mut health_bars: Query<(&VersusBaseHealthBar, &mut Sprite, &mut Transform)>,
+) {
+    for (health_bar, mut sprite, mut transform) in &mut health_bars {
+        let Some((health, _)) = commanders.iter().find(|(_, owner)| owner.0 == health_bar.owner) else {
+            continue;
+        };
+        let health_width = (health.current as f32 / health.max as f32) * 160.0;
+        sprite.custom_size = Some(Vec2::new(health_width, 10.0));
+        transform.translation.x = health_bar.original_x - (160.0 - health_width) / 2.0;
+    }
+}
+
+/// 回合结束判定：司令官被摧毁的玩家输掉这一局，双方司令官同时被摧毁为平局
+pub fn check_round_over(
+    mut commands: Commands,
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+    commanders: Query<(&Health, &CommanderOwner), With<Commander>>,
+    mut round_over_timers: Query<(Entity, &mut RoundOverTimer)>,
+    mut versus_match: ResMut<VersusMatch>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if *game_mode != GameMode::Versus {
+        return;
+    }
+
+    // 已经分出胜负，等待爆炸动画完成后显示比分
+    if let Ok((entity, mut round_over_timer)) = round_over_timers.single_mut() {
+        round_over_timer.0.tick(time.delta());
+        if round_over_timer.0.is_finished() {
+            commands.entity(entity).try_despawn();
+            next_state.set(GameState::VersusResults);
+        }
+        return;
+    }
+
+    let is_destroyed = |player_type: TankType| {
+        commanders
+            .iter()
+            .any(|(health, owner)| owner.0 == player_type && health.current == 0)
+    };
+    let round_winner = match (is_destroyed(TankType::Player1), is_destroyed(TankType::Player2)) {
+        (false, false) => return,
+        (true, true) => None,
+        (true, false) => Some(TankType::Player2),
+        (false, true) => Some(TankType::Player1),
+    };
+
+    versus_match.round_winner = round_winner;
+    if let Some(winner) = round_winner {
+        *versus_match.wins.entry(winner).or_default() += 1;
+    }
+
+    commands.spawn((
+        PlayingEntity,
+        RoundOverTimer(Timer::from_seconds(ROUND_OVER_DELAY, TimerMode::Once)),
+    ));
+}
+
+/// 生成对战比分界面
+pub fn spawn_versus_results(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    versus_match: Res<VersusMatch>,
+    match_options: Res<MatchOptions>,
+) {
+    let font: Handle<Font> = asset_server.load(crate::FONT_EN);
+    let rounds = match_options.versus_rounds;
+    let wins = |player_type| versus_match.wins.get(&player_type).copied().unwrap_or(0);
+
+    // 黑色背景，盖住战场
+    commands.spawn((
+        VersusResultsUI,
+        Sprite {
+            color: Color::srgb(0.0, 0.0, 0.0),
+            custom_size: Some(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
+            ..default()
+        },
+        Transform::from_xyz(0.0, 0.0, 20.0),
+    ));
+
+    // 本局结果
+    let round_result = match versus_match.round_winner {
+        Some(winner) => format!("{} Wins Round {}", player_label(winner), versus_match.round),
+        None => format!("Round {} Draw", versus_match.round),
+    };
+    commands.spawn((
+        VersusResultsUI,
+        Text2d(round_result),
+        TextFont {
+            font_size: 80.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 0.8, 0.0)),
+        Transform::from_xyz(0.0, 300.0, 21.0),
+    ));
+
+    // 比分
+    commands.spawn((
+        VersusResultsUI,
+        Text2d(format!(
+            "Player 1   {} : {}   Player 2",
+            wins(TankType::Player1),
+            wins(TankType::Player2)
+        )),
+        TextFont {
+            font_size: 56.0,
+            font: font.clone(),
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        Transform::from_xyz(0.0, 100.0, 21.0),
+    ));
+
+    // 整场比赛的胜者，或者赢下比赛还需要的局数
+    let (match_text, hint) = versus_match.match_winner(rounds).map_or_else(
+        || (format!("First to {} wins (Best of {rounds})", VersusMatch::wins_needed(rounds)), "SPACE for next round"),
+        |winner| (format!("{} Wins the Match!", player_label(winner)), "SPACE to return to menu"),
+    );
+    commands.spawn((
+        VersusResultsUI,
+        Text2d(match_text),
+        TextFont {
+            font_size: 40.0,
+            font,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        Transform::from_xyz(0.0, -80.0, 21.0),
+    ));
+
+    // 操作说明
+    commands.spawn((
+        VersusResultsUI,
+        Text2d(hint.to_string()),
+        TextFont {
+            font_size: 30.0,
+            ..default()
+        },
+        TextColor(Color::srgb(1.0, 1.0, 1.0)),
+        Transform::from_xyz(0.0, -400.0, 21.0),
+    ));
+}
+
+/// 比分界面输入：比赛分出胜负时空格返回主菜单，否则进入下一局
+pub fn handle_versus_results_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut versus_match: ResMut<VersusMatch>,
+    match_options: Res<MatchOptions>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Esc 键退出
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        std::process::exit(0);
+    }
+
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+
+    if versus_match.match_winner(match_options.versus_rounds).is_some() {
+        next_state.set(GameState::StartScreen);
+        return;
+    }
+
+    // 进入下一局
+    versus_match.round += 1;
+    next_state.set(GameState::StageIntro);
+}
+
+/// 清理对战比分界面
+pub fn despawn_versus_results(mut commands: Commands, query: Query<Entity, With<VersusResultsUI>>) {
+    for entity in query.iter() {
+        commands.entity(entity).try_despawn();
+    }
+}